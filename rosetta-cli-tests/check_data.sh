@@ -38,10 +38,20 @@ fi
 # move bootstrap_balances file to the specific rosetta-cli-conf directory
 mv bootstrap_balances.json $CONF_DIR
 
+# rosetta-cli starts at the genesis block reported by the server, which must be the milestone after the sep_index the
+# bootstrap balances belong to; the server reports it if it was started with --snapshot-dir
 SEP_INDEX=$(cat sep_index)
-START_MS=`expr $SEP_INDEX + 1`
+GENESIS_INDEX=$(curl -s -X POST "$(jq -r '.online_url' $CONF_DIR/config.json)/network/status" \
+  -H "Content-Type: application/json" \
+  -d "{\"network_identifier\": $(jq -c '.network' $CONF_DIR/config.json)}" | jq '.genesis_block_identifier.index')
 
-cat <<< $(jq --argjson START_MS "$START_MS" '.data.start_index |= $START_MS' $CONF_DIR/config.json) > $CONF_DIR/config.json
+if [[ "$GENESIS_INDEX" != "$(expr $SEP_INDEX + 1)" ]]; then
+  echo "the genesis block $GENESIS_INDEX of the Rosetta API does not follow the sep_index $SEP_INDEX of the snapshot"
+  exit 1
+fi
+
+# a start_index patched in by earlier versions of this script would override the genesis block
+cat <<< $(jq 'del(.data.start_index)' $CONF_DIR/config.json) > $CONF_DIR/config.json
 
 # clean up artifacts
 rm delta_snapshot.bin
@@ -1,15 +1,175 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::ApiError, RosettaConfig};
+use crate::{consts, error::ApiError, metrics, RosettaConfig};
 
-use bee_message::prelude::*;
+use bee_message::{prelude::*, Message};
 use bee_rest_api::types::responses::*;
 
 use bee_rest_api::types::dtos::PeerDto;
+use futures::future::try_join_all;
 use iota_client::Client;
+use log::{info, warn};
+use tokio::sync::RwLock;
 
-pub async fn build_client(options: &RosettaConfig) -> Result<Client, ApiError> {
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Holds one client per configured node and shares them between all request handlers. Clients are built on first use;
+/// nodes that fail a health check are skipped until they become reachable again, so requests fail over to the next
+/// node in the configured order.
+#[derive(Clone)]
+pub struct ClientPool {
+    rosetta_config: RosettaConfig,
+    nodes: Arc<Vec<PooledNode>>,
+}
+
+struct PooledNode {
+    url: String,
+    client: RwLock<Option<Arc<Client>>>,
+    healthy: AtomicBool,
+}
+
+impl ClientPool {
+    pub fn new(rosetta_config: &RosettaConfig) -> Self {
+        let nodes = rosetta_config
+            .node_urls
+            .iter()
+            .map(|url| PooledNode {
+                url: url.clone(),
+                client: RwLock::new(None),
+                healthy: AtomicBool::new(true),
+            })
+            .collect();
+
+        Self {
+            rosetta_config: rosetta_config.clone(),
+            nodes: Arc::new(nodes),
+        }
+    }
+
+    pub fn quorum_size(&self) -> Option<usize> {
+        self.rosetta_config.quorum_size
+    }
+
+    /// Returns the client of the first healthy node.
+    pub async fn get(&self) -> Result<Arc<Client>, ApiError> {
+        Ok(self.get_many(1).await?.remove(0))
+    }
+
+    /// Returns the clients of the first `count` healthy nodes.
+    pub async fn get_many(&self, count: usize) -> Result<Vec<Arc<Client>>, ApiError> {
+        let mut clients = Vec::new();
+
+        // nodes that are known to be unhealthy are only tried if not enough healthy nodes are left
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) =
+            self.nodes.iter().partition(|node| node.healthy.load(Ordering::Relaxed));
+
+        for node in healthy.into_iter().chain(unhealthy) {
+            if let Some(client) = self.connect(node).await {
+                clients.push(client);
+                if clients.len() == count {
+                    return Ok(clients);
+                }
+            }
+        }
+
+        Err(ApiError::NodeUnreachable(format!(
+            "not enough nodes reachable: {} of {} required",
+            clients.len(),
+            count
+        )))
+    }
+
+    async fn connect(&self, node: &PooledNode) -> Option<Arc<Client>> {
+        if node.healthy.load(Ordering::Relaxed) {
+            if let Some(client) = node.client.read().await.as_ref() {
+                return Some(client.clone());
+            }
+        }
+        self.check_health(node).await
+    }
+
+    async fn check_health(&self, node: &PooledNode) -> Option<Arc<Client>> {
+        let built_client = node.client.read().await.clone();
+
+        let client = match built_client {
+            Some(client) => client,
+            None => match build_client(&node.url, &self.rosetta_config).await {
+                Ok(client) => Arc::new(client),
+                Err(e) => {
+                    warn!("can not build client for node {}: {:?}", node.url, e);
+                    node.healthy.store(false, Ordering::Relaxed);
+                    return None;
+                }
+            },
+        };
+
+        match get_node_info(&client).await {
+            Ok(node_info) => {
+                metrics::set_milestone_gap(
+                    &node.url,
+                    node_info.confirmed_milestone_index,
+                    node_info.latest_milestone_index,
+                );
+                *node.client.write().await = Some(client.clone());
+                if !node.healthy.swap(true, Ordering::Relaxed) {
+                    info!("node {} is reachable again", node.url);
+                }
+                Some(client)
+            }
+            Err(e) => {
+                warn!("node {} unreachable: {:?}", node.url, e);
+                node.healthy.store(false, Ordering::Relaxed);
+                *node.client.write().await = None;
+                None
+            }
+        }
+    }
+}
+
+pub async fn watch_node_health(client_pool: ClientPool) {
+    let mut interval = tokio::time::interval(consts::NODE_HEALTH_CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        for node in client_pool.nodes.iter() {
+            // nodes that were never used are checked on first use
+            let built = node.client.read().await.is_some();
+            if built || !node.healthy.load(Ordering::Relaxed) {
+                let _ = client_pool.check_health(node).await;
+            }
+        }
+    }
+}
+
+/// Runs the same read against the given clients and only returns its result if all nodes agree on it. Only the parts
+/// of the responses selected by `key` are compared, so fields that may differ between synced nodes, like the ledger
+/// index, do not prevent a quorum.
+async fn read_with_quorum<T, K, F, Fut>(clients: &[Arc<Client>], read: F, key: fn(&T) -> K) -> Result<T, ApiError>
+where
+    K: PartialEq,
+    F: Fn(Arc<Client>) -> Fut,
+    Fut: Future<Output = Result<T, ApiError>>,
+{
+    let mut responses = try_join_all(clients.iter().cloned().map(read)).await?;
+
+    let first = key(&responses[0]);
+    if responses.iter().skip(1).any(|response| key(response) != first) {
+        return Err(ApiError::Retriable(
+            "quorum not reached: nodes returned different data".to_string(),
+        ));
+    }
+
+    Ok(responses.swap_remove(0))
+}
+
+pub async fn build_client(node_url: &str, options: &RosettaConfig) -> Result<Client, ApiError> {
     let mut builder = Client::builder();
 
     if cfg!(feature = "dummy_node") {
@@ -18,7 +178,7 @@ pub async fn build_client(options: &RosettaConfig) -> Result<Client, ApiError> {
 
     builder = builder
         .with_network(&options.network)
-        .with_node(&options.node_url)
+        .with_node(node_url)
         .map_err(|e| ApiError::NonRetriable(format!("unable to build client: {}", e)))?;
 
     Ok(builder
@@ -27,45 +187,101 @@ pub async fn build_client(options: &RosettaConfig) -> Result<Client, ApiError> {
         .map_err(|e| ApiError::NonRetriable(format!("unable to build client: {}", e)))?)
 }
 
+/// Maps a failed node request to an API error. Transport errors and errors on the node side are worth a retry, while
+/// requests the node rejected will fail again.
+pub(crate) fn node_error(context: &str, error: iota_client::Error) -> ApiError {
+    let message = format!("{}: {}", context, error);
+    match &error {
+        iota_client::Error::ResponseError(status, _) => match status {
+            400..=499 if *status != 408 && *status != 429 => ApiError::NonRetriable(message),
+            _ => ApiError::Retriable(message),
+        },
+        iota_client::Error::ReqwestError(e) if e.is_decode() => ApiError::NonRetriable(message),
+        iota_client::Error::ReqwestError(_) | iota_client::Error::SyncedNodePoolEmpty => {
+            ApiError::NodeUnreachable(message)
+        }
+        _ => ApiError::NonRetriable(message),
+    }
+}
+
 pub async fn get_output(output_id: OutputId, client: &Client) -> Result<OutputResponse, ApiError> {
+    let _timer = metrics::node_call_timer("get_output");
+
     client
         .get_output(&(output_id.into()))
         .await
-        .map_err(|e| ApiError::NonRetriable(format!("can not get output: {}", e)))
+        .map_err(|e| node_error("can not get output", e))
+}
+
+pub async fn get_message(message_id: &MessageId, client: &Client) -> Result<Message, ApiError> {
+    let _timer = metrics::node_call_timer("get_message");
+
+    client
+        .get_message()
+        .data(message_id)
+        .await
+        .map_err(|e| node_error("can not get message", e))
+}
+
+pub async fn get_message_metadata(
+    message_id: &MessageId,
+    client: &Client,
+) -> Result<MessageMetadataResponse, ApiError> {
+    let _timer = metrics::node_call_timer("get_message_metadata");
+
+    client
+        .get_message()
+        .metadata(message_id)
+        .await
+        .map_err(|e| node_error("can not get message metadata", e))
+}
+
+pub async fn get_tips(client: &Client) -> Result<Vec<MessageId>, ApiError> {
+    let _timer = metrics::node_call_timer("get_tips");
+
+    client.get_tips().await.map_err(|e| node_error("can not get tips", e))
 }
 
 pub async fn get_unspent_outputs_of_address(
     bech32_addr: &str,
     client: &Client,
 ) -> Result<OutputsAddressResponse, ApiError> {
+    let _timer = metrics::node_call_timer("get_unspent_outputs_of_address");
+
     match client
         .get_address()
         .outputs_response(&bech32_addr.to_string(), Default::default())
         .await
     {
         Ok(response) => Ok(response),
-        Err(e) => return Err(ApiError::NonRetriable(format!("can not get outputs of address: {}", e))),
+        Err(e) => return Err(node_error("can not get outputs of address", e)),
     }
 }
 
 pub async fn get_balance_of_address(bech32_addr: &str, client: &Client) -> Result<BalanceAddressResponse, ApiError> {
+    let _timer = metrics::node_call_timer("get_balance_of_address");
+
     match client.get_address().balance(bech32_addr).await {
         Ok(balance) => Ok(balance),
-        Err(e) => return Err(ApiError::NonRetriable(format!("unable to get balance: {}", e))),
+        Err(e) => return Err(node_error("unable to get balance", e)),
     }
 }
 
 pub async fn get_milestone(milestone_index: u32, client: &Client) -> Result<iota_client::MilestoneResponse, ApiError> {
+    let _timer = metrics::node_call_timer("get_milestone");
+
     match client.get_milestone(milestone_index).await {
         Ok(milestone) => Ok(milestone),
-        Err(e) => return Err(ApiError::NonRetriable(format!("can not get milestone: {}", e))),
+        Err(e) => return Err(node_error("can not get milestone", e)),
     }
 }
 
 async fn get_confirmed_milestone_index(client: &Client) -> Result<u32, ApiError> {
+    let _timer = metrics::node_call_timer("get_confirmed_milestone_index");
+
     match client.get_info().await {
         Ok(res) => Ok(res.nodeinfo.confirmed_milestone_index),
-        Err(e) => return Err(ApiError::NonRetriable(format!("unable to get node info: {}", e))),
+        Err(e) => return Err(node_error("unable to get node info", e)),
     }
 }
 
@@ -75,9 +291,11 @@ pub async fn get_confirmed_milestone(client: &Client) -> Result<iota_client::Mil
 }
 
 pub async fn get_latest_milestone_index(client: &Client) -> Result<u32, ApiError> {
+    let _timer = metrics::node_call_timer("get_latest_milestone_index");
+
     match client.get_info().await {
         Ok(res) => Ok(res.nodeinfo.latest_milestone_index),
-        Err(e) => return Err(ApiError::NonRetriable(format!("unable to get node info: {}", e))),
+        Err(e) => return Err(node_error("unable to get node info", e)),
     }
 }
 
@@ -87,22 +305,35 @@ pub async fn get_latest_milestone(client: &Client) -> Result<iota_client::Milest
 }
 
 pub async fn get_node_info(client: &Client) -> Result<InfoResponse, ApiError> {
+    let _timer = metrics::node_call_timer("get_node_info");
+
     match client.get_info().await {
         Ok(res) => Ok(res.nodeinfo),
-        Err(e) => return Err(ApiError::NonRetriable(format!("unable to get node info: {}", e))),
+        Err(e) => return Err(node_error("unable to get node info", e)),
     }
 }
 
+pub async fn get_treasury(client: &Client) -> Result<TreasuryResponse, ApiError> {
+    let _timer = metrics::node_call_timer("get_treasury");
+
+    client
+        .get_treasury()
+        .await
+        .map_err(|e| node_error("can not get treasury", e))
+}
+
 pub async fn get_pruning_index(client: &Client) -> Result<u32, ApiError> {
     let node_info = get_node_info(client).await?;
     Ok(node_info.pruning_index)
 }
 
 pub async fn get_peers(client: &Client) -> Result<Vec<PeerDto>, ApiError> {
+    let _timer = metrics::node_call_timer("get_peers");
+
     client
         .get_peers()
         .await
-        .map_err(|e| ApiError::NonRetriable(format!("unable to get peers: {}", e)))
+        .map_err(|e| node_error("unable to get peers", e))
 }
 
 pub async fn get_utxo_changes(milestone_index: u32, client: &Client) -> Result<UtxoChangesResponse, ApiError> {
@@ -113,9 +344,53 @@ pub async fn get_utxo_changes(milestone_index: u32, client: &Client) -> Result<U
             milestone_index
         )));
     } else {
+        let _timer = metrics::node_call_timer("get_utxo_changes");
+
         client
             .get_milestone_utxo_changes(milestone_index)
             .await
-            .map_err(|e| ApiError::NonRetriable(format!("can not get uxto-changes: {}", e)))
+            .map_err(|e| node_error("can not get uxto-changes", e))
+    }
+}
+
+pub async fn get_utxo_changes_from_pool(
+    milestone_index: u32,
+    client_pool: &ClientPool,
+) -> Result<UtxoChangesResponse, ApiError> {
+    match client_pool.quorum_size() {
+        Some(quorum_size) => {
+            let clients = client_pool.get_many(quorum_size).await?;
+            read_with_quorum(
+                &clients,
+                |client| async move { get_utxo_changes(milestone_index, &client).await },
+                |utxo_changes| {
+                    (
+                        utxo_changes.index,
+                        utxo_changes.created_outputs.clone(),
+                        utxo_changes.consumed_outputs.clone(),
+                    )
+                },
+            )
+            .await
+        }
+        None => get_utxo_changes(milestone_index, &*client_pool.get().await?).await,
+    }
+}
+
+pub async fn get_balance_of_address_from_pool(
+    bech32_addr: &str,
+    client_pool: &ClientPool,
+) -> Result<BalanceAddressResponse, ApiError> {
+    match client_pool.quorum_size() {
+        Some(quorum_size) => {
+            let clients = client_pool.get_many(quorum_size).await?;
+            read_with_quorum(
+                &clients,
+                |client| async move { get_balance_of_address(bech32_addr, &client).await },
+                |balance| (balance.balance, balance.dust_allowed),
+            )
+            .await
+        }
+        None => get_balance_of_address(bech32_addr, &*client_pool.get().await?).await,
     }
 }
@@ -3,7 +3,7 @@
 
 use structopt::StructOpt;
 
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 #[derive(Clone, Debug, StructOpt)]
 pub struct RosettaConfig {
@@ -13,10 +13,26 @@ pub struct RosettaConfig {
     pub network: String,
     #[structopt(long)]
     pub bech32_hrp: String,
+    #[structopt(long = "node-url", required = true)]
+    pub node_urls: Vec<String>,
     #[structopt(long)]
-    pub node_url: String,
+    pub quorum_size: Option<usize>,
     #[structopt(long, default_value = "online")]
     pub mode: RosettaMode,
+    /// Directory with the `sep_index` and `bootstrap_balances.json` files of rosetta-iota-snapshot; enables historical
+    /// balance lookups
+    #[structopt(long, parse(from_os_str))]
+    pub snapshot_dir: Option<PathBuf>,
+    /// Directory in which built blocks are cached; cached blocks can still be served after the node pruned them
+    #[structopt(long, parse(from_os_str))]
+    pub block_cache_dir: Option<PathBuf>,
+    /// Index of the genesis block; defaults to the milestone after the `sep_index` of the snapshot directory if one is
+    /// set, otherwise to 1
+    #[structopt(long)]
+    pub genesis_index: Option<u32>,
+    /// Lowest index that is reported as oldest block, even if older blocks are available
+    #[structopt(long)]
+    pub oldest_index: Option<u32>,
 }
 
 #[derive(Clone, Debug, StructOpt, PartialEq)]
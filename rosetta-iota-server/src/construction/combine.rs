@@ -2,19 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    construction::{deserialize_unsigned_transaction, serialize_signed_transaction},
+    construction::encoding::{deserialize_unsigned_transaction, serialize_signed_transaction},
     error::ApiError,
     is_wrong_network,
+    state::ServerState,
     types::*,
     RosettaConfig,
 };
 
 use bee_message::prelude::*;
+use bee_rest_api::types::responses::OutputResponse;
 
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::TryFrom};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -33,55 +35,88 @@ pub struct ConstructionCombineResponse {
 pub async fn combine(
     request: ConstructionCombineRequest,
     rosetta_config: RosettaConfig,
+    _server_state: ServerState,
 ) -> Result<ConstructionCombineResponse, ApiError> {
     debug!("/construction/combine");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
-    let unsigned_transaction = deserialize_unsigned_transaction(&request.unsigned_transaction);
+    let unsigned_transaction = deserialize_unsigned_transaction(&request.unsigned_transaction)?;
 
     let Essence::Regular(regular_essence) = &unsigned_transaction.essence();
 
-    if regular_essence.inputs().len() != request.signatures.len() {
-        return Err(ApiError::NonRetriable(
-            "for every input a signature must be provided".to_string(),
-        ));
+    // all inputs sign the same essence hash, so an address needs only one signature for all of its inputs
+    let mut signatures_of_addresses: HashMap<Address, Ed25519Signature> = HashMap::new();
+
+    for signature in request.signatures {
+        // get address for which the signature was produced
+        let bech32_addr = signature.signing_payload.account_identifier.address;
+        let address = Address::try_from_bech32(&bech32_addr)
+            .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", bech32_addr, e)))?;
+
+        let signature = {
+            let mut public_key_bytes = [0u8; 32];
+            let mut signature_bytes = [0u8; 64];
+            hex::decode_to_slice(signature.public_key.hex_bytes.clone(), &mut public_key_bytes)
+                .map_err(|e| ApiError::NonRetriable(format!("invalid public key: {}", e)))?;
+            hex::decode_to_slice(signature.hex_bytes.clone(), &mut signature_bytes)
+                .map_err(|e| ApiError::NonRetriable(format!("invalid signature: {}", e)))?;
+            Ed25519Signature::new(public_key_bytes, signature_bytes)
+        };
+
+        // the same signature may be provided once per input, but an address can not sign with different signatures
+        match signatures_of_addresses.get(&address) {
+            Some(known_signature) if *known_signature != signature => {
+                return Err(ApiError::NonRetriable(format!(
+                    "different signatures provided for address {}",
+                    bech32_addr
+                )));
+            }
+            Some(_) => {}
+            None => {
+                signatures_of_addresses.insert(address, signature);
+            }
+        }
     }
 
     let mut unlock_blocks = Vec::new();
     type SignatureUnlockBlockIndex = u16;
-    let mut index_of_signature_unlock_block_with_address: HashMap<String, SignatureUnlockBlockIndex> = HashMap::new();
+    let mut index_of_signature_unlock_block_with_address: HashMap<Address, SignatureUnlockBlockIndex> = HashMap::new();
 
-    for signature in request.signatures {
-        // get address for which the signature was produced
-        let bech32_addr = signature.signing_payload.account_identifier.address;
+    // the unlock blocks must be in the order of the inputs
+    for input in regular_essence.inputs() {
+        let address = input_address(input, unsigned_transaction.inputs_metadata())?;
 
         // check if a Signature Unlock Block already was added for the address
-        if let Some(index) = index_of_signature_unlock_block_with_address.get(&bech32_addr) {
+        if let Some(index) = index_of_signature_unlock_block_with_address.get(&address) {
             // build a Reference Unlock Block
             unlock_blocks.push(UnlockBlock::Reference(ReferenceUnlock::new(*index).unwrap()));
         } else {
             // build a Signature Unlock Block
-
-            let signature = {
-                let mut public_key_bytes = [0u8; 32];
-                let mut signature_bytes = [0u8; 64];
-                hex::decode_to_slice(signature.public_key.hex_bytes.clone(), &mut public_key_bytes)
-                    .map_err(|e| ApiError::NonRetriable(format!("invalid public key: {}", e)))?;
-                hex::decode_to_slice(signature.hex_bytes.clone(), &mut signature_bytes)
-                    .map_err(|e| ApiError::NonRetriable(format!("invalid signature: {}", e)))?;
-                Ed25519Signature::new(public_key_bytes, signature_bytes)
-            };
+            let signature = signatures_of_addresses.remove(&address).ok_or_else(|| {
+                ApiError::NonRetriable(format!(
+                    "no signature provided for address {}",
+                    address.to_bech32(&rosetta_config.bech32_hrp)
+                ))
+            })?;
 
             unlock_blocks.push(UnlockBlock::Signature(SignatureUnlock::Ed25519(signature)));
 
             // memorise the address and index of the Signature Unlock Block
-            index_of_signature_unlock_block_with_address.insert(bech32_addr, (unlock_blocks.len() - 1) as u16);
+            index_of_signature_unlock_block_with_address.insert(address, (unlock_blocks.len() - 1) as u16);
         }
     }
 
+    // a signature that unlocks no input would be dropped silently
+    if let Some(address) = signatures_of_addresses.keys().next() {
+        return Err(ApiError::NonRetriable(format!(
+            "signature provided for address {} that owns no input",
+            address.to_bech32(&rosetta_config.bech32_hrp)
+        )));
+    }
+
     let transaction = TransactionPayload::builder()
         .with_essence(unsigned_transaction.essence().clone())
         .with_unlock_blocks(UnlockBlocks::new(unlock_blocks).unwrap())
@@ -91,6 +126,27 @@ pub async fn combine(
     let signed_transaction = SignedTransaction::new(transaction, unsigned_transaction.inputs_metadata().clone());
 
     Ok(ConstructionCombineResponse {
-        signed_transaction: serialize_signed_transaction(&signed_transaction),
+        signed_transaction: serialize_signed_transaction(&signed_transaction)?,
     })
 }
+
+fn input_address(input: &Input, inputs_metadata: &HashMap<String, OutputResponse>) -> Result<Address, ApiError> {
+    let utxo_input = match input {
+        Input::Utxo(i) => i,
+        _ => return Err(ApiError::NonRetriable("unknown input type".to_string())),
+    };
+
+    let input_metadata = match inputs_metadata.get(&utxo_input.to_string()) {
+        Some(metadata) => metadata,
+        None => return Err(ApiError::NonRetriable("missing metadata for input".to_string())),
+    };
+
+    let output = Output::try_from(&input_metadata.output)
+        .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
+
+    match output {
+        Output::SignatureLockedSingle(output) => Ok(*output.address()),
+        Output::SignatureLockedDustAllowance(output) => Ok(*output.address()),
+        _ => Err(ApiError::NonRetriable("unknown output type of input".to_string())),
+    }
+}
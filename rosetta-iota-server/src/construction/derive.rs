@@ -1,7 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::ApiError, is_wrong_network, types::*, RosettaConfig};
+use crate::{error::ApiError, is_wrong_network, state::ServerState, types::*, RosettaConfig};
 
 use bee_message::prelude::{Address, Ed25519Address};
 
@@ -26,11 +26,12 @@ pub struct ConstructionDeriveResponse {
 pub async fn derive(
     request: ConstructionDeriveRequest,
     rosetta_config: RosettaConfig,
+    _server_state: ServerState,
 ) -> Result<ConstructionDeriveResponse, ApiError> {
     debug!("/construction/derive");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     let public_key_bytes = hex::decode(request.public_key.hex_bytes)
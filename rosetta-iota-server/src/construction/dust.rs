@@ -0,0 +1,94 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consts::{DUST_ALLOWANCE_DIVISOR, DUST_THRESHOLD, MAX_DUST_OUTPUTS},
+    error::ApiError,
+    types::DustAllowanceState,
+};
+
+use bee_message::prelude::*;
+
+use std::collections::HashMap;
+
+/// Checks that after the transaction, no address owns more dust outputs than its dust allowance allows. `consumed` are
+/// the outputs spent by the inputs of the transaction, `dust_allowances` the states of the addresses before it.
+pub fn validate_dust(
+    created: &[Output],
+    consumed: &[Output],
+    dust_allowances: &HashMap<Address, DustAllowanceState>,
+    bech32_hrp: &str,
+) -> Result<(), ApiError> {
+    let mut states: HashMap<Address, DustAllowanceState> = HashMap::new();
+    // only addresses that receive dust or lose dust allowance can become invalid
+    let mut affected = Vec::new();
+
+    for &(outputs, is_created) in [(created, true), (consumed, false)].iter() {
+        for output in outputs {
+            let address = match output_address(output) {
+                Some(address) => address,
+                None => continue,
+            };
+            let state = states
+                .entry(*address)
+                .or_insert_with(|| dust_allowances.get(address).copied().unwrap_or_default());
+            apply_output(state, output, is_created);
+
+            let affects_dust = match output {
+                Output::SignatureLockedSingle(output) => is_created && output.amount() < DUST_THRESHOLD,
+                Output::SignatureLockedDustAllowance(_) => !is_created,
+                _ => false,
+            };
+            if affects_dust && !affected.contains(address) {
+                affected.push(*address);
+            }
+        }
+    }
+
+    for address in affected {
+        let state = states[&address];
+        let allowed = (state.dust_allowance / DUST_ALLOWANCE_DIVISOR).min(MAX_DUST_OUTPUTS);
+        if state.dust_outputs > allowed {
+            return Err(ApiError::InvalidDustOutput(format!(
+                "address {} would own {} dust outputs, but its dust allowance of {} only allows {}; outputs below {} \
+                 need a dust allowance output on the receiving address, which can be created in the same transaction",
+                address.to_bech32(bech32_hrp),
+                state.dust_outputs,
+                state.dust_allowance,
+                allowed,
+                DUST_THRESHOLD
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn output_address(output: &Output) -> Option<&Address> {
+    match output {
+        Output::SignatureLockedSingle(output) => Some(output.address()),
+        Output::SignatureLockedDustAllowance(output) => Some(output.address()),
+        _ => None,
+    }
+}
+
+/// Adds a created output to the dust allowance state of its address, or removes a consumed one.
+pub(crate) fn apply_output(state: &mut DustAllowanceState, output: &Output, created: bool) {
+    match output {
+        Output::SignatureLockedSingle(output) if output.amount() < DUST_THRESHOLD => {
+            if created {
+                state.dust_outputs += 1;
+            } else {
+                state.dust_outputs = state.dust_outputs.saturating_sub(1);
+            }
+        }
+        Output::SignatureLockedDustAllowance(output) => {
+            if created {
+                state.dust_allowance += output.amount();
+            } else {
+                state.dust_allowance = state.dust_allowance.saturating_sub(output.amount());
+            }
+        }
+        _ => {}
+    }
+}
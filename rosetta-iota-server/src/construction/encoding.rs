@@ -0,0 +1,229 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encoding of the unsigned and signed transactions that are passed between the construction endpoints.
+//!
+//! A transaction is encoded as a hex string of a versioned binary envelope:
+//!
+//! | field           | type                                                 |
+//! |-----------------|------------------------------------------------------|
+//! | version         | `u8`, currently `1`                                  |
+//! | kind            | `u8`, `0` for unsigned and `1` for signed            |
+//! | transaction     | the packed `Essence` or `TransactionPayload`         |
+//! | inputs metadata | for every input of the essence, in the same order    |
+//!
+//! The metadata of an input starts with a `u8` that is `0` if the metadata is missing. Otherwise it is followed by the
+//! id of the message that created the output, whether the output is spent (`u8`), the ledger index (`u32`) and the
+//! packed output. The transaction id and the output index are taken from the input itself. Malformed metadata is not
+//! encoded as missing; the transaction can not be encoded then.
+//!
+//! Transactions encoded by older versions as hex encoded JSON can still be decoded.
+
+use crate::{
+    error::ApiError,
+    types::{SignedTransaction, UnsignedTransaction},
+};
+
+use bee_common::packable::Packable;
+use bee_message::prelude::*;
+use bee_rest_api::types::{dtos::OutputDto, responses::OutputResponse};
+
+use std::{collections::HashMap, convert::TryFrom, io::Read};
+
+const ENVELOPE_VERSION: u8 = 1;
+const UNSIGNED_TRANSACTION_KIND: u8 = 0;
+const SIGNED_TRANSACTION_KIND: u8 = 1;
+// the first byte of the JSON encoding used before the binary envelope
+const LEGACY_JSON_PREFIX: u8 = b'{';
+
+pub fn serialize_unsigned_transaction(unsigned_transaction: &UnsignedTransaction) -> Result<String, ApiError> {
+    let mut bytes = vec![ENVELOPE_VERSION, UNSIGNED_TRANSACTION_KIND];
+    unsigned_transaction
+        .essence()
+        .pack(&mut bytes)
+        .map_err(|e| ApiError::NonRetriable(format!("can not pack essence: {}", e)))?;
+    pack_inputs_metadata(
+        unsigned_transaction.essence(),
+        unsigned_transaction.inputs_metadata(),
+        &mut bytes,
+    )?;
+    Ok(hex::encode(bytes))
+}
+
+pub fn deserialize_unsigned_transaction(string: &str) -> Result<UnsignedTransaction, ApiError> {
+    let bytes = decode_hex(string)?;
+
+    if bytes.first() == Some(&LEGACY_JSON_PREFIX) {
+        return serde_json::from_slice(&bytes)
+            .map_err(|e| ApiError::NonRetriable(format!("can not deserialize unsigned transaction: {}", e)));
+    }
+
+    let reader = &mut open_envelope(&bytes, UNSIGNED_TRANSACTION_KIND)?;
+    let essence =
+        Essence::unpack(reader).map_err(|e| ApiError::NonRetriable(format!("can not unpack essence: {}", e)))?;
+    let inputs_metadata = unpack_inputs_metadata(&essence, reader)?;
+    close_envelope(reader)?;
+
+    Ok(UnsignedTransaction::new(essence, inputs_metadata))
+}
+
+pub fn serialize_signed_transaction(signed_transaction: &SignedTransaction) -> Result<String, ApiError> {
+    let mut bytes = vec![ENVELOPE_VERSION, SIGNED_TRANSACTION_KIND];
+    signed_transaction
+        .transaction()
+        .pack(&mut bytes)
+        .map_err(|e| ApiError::NonRetriable(format!("can not pack transaction: {}", e)))?;
+    pack_inputs_metadata(
+        signed_transaction.transaction().essence(),
+        signed_transaction.inputs_metadata(),
+        &mut bytes,
+    )?;
+    Ok(hex::encode(bytes))
+}
+
+pub fn deserialize_signed_transaction(string: &str) -> Result<SignedTransaction, ApiError> {
+    let bytes = decode_hex(string)?;
+
+    if bytes.first() == Some(&LEGACY_JSON_PREFIX) {
+        return serde_json::from_slice(&bytes)
+            .map_err(|e| ApiError::NonRetriable(format!("can not deserialize signed transaction: {}", e)));
+    }
+
+    let reader = &mut open_envelope(&bytes, SIGNED_TRANSACTION_KIND)?;
+    let transaction = TransactionPayload::unpack(reader)
+        .map_err(|e| ApiError::NonRetriable(format!("can not unpack transaction: {}", e)))?;
+    let inputs_metadata = unpack_inputs_metadata(transaction.essence(), reader)?;
+    close_envelope(reader)?;
+
+    Ok(SignedTransaction::new(transaction, inputs_metadata))
+}
+
+fn decode_hex(string: &str) -> Result<Vec<u8>, ApiError> {
+    hex::decode(string).map_err(|e| ApiError::NonRetriable(format!("can not decode transaction: {}", e)))
+}
+
+fn open_envelope(bytes: &[u8], expected_kind: u8) -> Result<&[u8], ApiError> {
+    match bytes {
+        [ENVELOPE_VERSION, kind, rest @ ..] if *kind == expected_kind => Ok(rest),
+        [ENVELOPE_VERSION, UNSIGNED_TRANSACTION_KIND, ..] => Err(ApiError::NonRetriable(
+            "expected a signed transaction, but got an unsigned one".to_string(),
+        )),
+        [ENVELOPE_VERSION, SIGNED_TRANSACTION_KIND, ..] => Err(ApiError::NonRetriable(
+            "expected an unsigned transaction, but got a signed one".to_string(),
+        )),
+        [ENVELOPE_VERSION, kind, ..] => Err(ApiError::NonRetriable(format!("unknown kind of transaction: {}", kind))),
+        [ENVELOPE_VERSION] => Err(ApiError::NonRetriable("truncated transaction".to_string())),
+        [version, ..] => Err(ApiError::NonRetriable(format!(
+            "unsupported version of transaction encoding: {}",
+            version
+        ))),
+        [] => Err(ApiError::NonRetriable("empty transaction".to_string())),
+    }
+}
+
+fn close_envelope(reader: &mut &[u8]) -> Result<(), ApiError> {
+    if !reader.is_empty() {
+        return Err(ApiError::NonRetriable(format!(
+            "{} trailing bytes after transaction",
+            reader.len()
+        )));
+    }
+    Ok(())
+}
+
+fn pack_inputs_metadata(
+    essence: &Essence,
+    inputs_metadata: &HashMap<String, OutputResponse>,
+    bytes: &mut Vec<u8>,
+) -> Result<(), ApiError> {
+    let Essence::Regular(regular_essence) = essence;
+
+    for input in regular_essence.inputs() {
+        let input_metadata = match input {
+            Input::Utxo(utxo_input) => inputs_metadata.get(&utxo_input.output_id().to_string()),
+            _ => None,
+        };
+
+        match input_metadata {
+            Some(input_metadata) => {
+                bytes.push(1);
+                bytes.extend(pack_input_metadata(input_metadata)?);
+            }
+            None => bytes.push(0),
+        }
+    }
+
+    Ok(())
+}
+
+fn pack_input_metadata(input_metadata: &OutputResponse) -> Result<Vec<u8>, ApiError> {
+    let message_id = input_metadata
+        .message_id
+        .parse::<MessageId>()
+        .map_err(|e| ApiError::NonRetriable(format!("invalid message id: {}", e)))?;
+    let output = Output::try_from(&input_metadata.output)
+        .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
+
+    let mut bytes = Vec::new();
+    message_id
+        .pack(&mut bytes)
+        .map_err(|e| ApiError::NonRetriable(format!("can not pack message id of input: {}", e)))?;
+    input_metadata
+        .is_spent
+        .pack(&mut bytes)
+        .map_err(|e| ApiError::NonRetriable(format!("can not pack metadata of input: {}", e)))?;
+    input_metadata
+        .ledger_index
+        .pack(&mut bytes)
+        .map_err(|e| ApiError::NonRetriable(format!("can not pack metadata of input: {}", e)))?;
+    output
+        .pack(&mut bytes)
+        .map_err(|e| ApiError::NonRetriable(format!("can not pack output of input: {}", e)))?;
+
+    Ok(bytes)
+}
+
+fn unpack_inputs_metadata<R: Read + ?Sized>(
+    essence: &Essence,
+    reader: &mut R,
+) -> Result<HashMap<String, OutputResponse>, ApiError> {
+    let Essence::Regular(regular_essence) = essence;
+
+    let mut inputs_metadata = HashMap::new();
+
+    for input in regular_essence.inputs() {
+        let has_metadata = u8::unpack(reader)
+            .map_err(|e| ApiError::NonRetriable(format!("can not unpack metadata of input: {}", e)))?;
+        if has_metadata == 0 {
+            continue;
+        }
+
+        let output_id = match input {
+            Input::Utxo(utxo_input) => *utxo_input.output_id(),
+            _ => return Err(ApiError::NonRetriable("unknown input type".to_string())),
+        };
+
+        let message_id = MessageId::unpack(reader)
+            .map_err(|e| ApiError::NonRetriable(format!("can not unpack message id of input: {}", e)))?;
+        let is_spent = bool::unpack(reader)
+            .map_err(|e| ApiError::NonRetriable(format!("can not unpack metadata of input: {}", e)))?;
+        let ledger_index = u32::unpack(reader)
+            .map_err(|e| ApiError::NonRetriable(format!("can not unpack metadata of input: {}", e)))?;
+        let output = Output::unpack(reader)
+            .map_err(|e| ApiError::NonRetriable(format!("can not unpack output of input: {}", e)))?;
+
+        inputs_metadata.insert(
+            output_id.to_string(),
+            OutputResponse {
+                message_id: message_id.to_string(),
+                transaction_id: output_id.transaction_id().to_string(),
+                output_index: output_id.index(),
+                is_spent,
+                output: OutputDto::from(&output),
+                ledger_index,
+            },
+        );
+    }
+
+    Ok(inputs_metadata)
+}
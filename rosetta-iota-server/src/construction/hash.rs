@@ -1,7 +1,10 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{construction::deserialize_signed_transaction, error::ApiError, is_wrong_network, types::*, RosettaConfig};
+use crate::{
+    construction::encoding::deserialize_signed_transaction, error::ApiError, is_wrong_network, state::ServerState,
+    types::*, RosettaConfig,
+};
 
 use log::debug;
 use serde::{Deserialize, Serialize};
@@ -22,14 +25,15 @@ pub struct ConstructionHashResponse {
 pub async fn hash(
     request: ConstructionHashRequest,
     rosetta_config: RosettaConfig,
+    _server_state: ServerState,
 ) -> Result<ConstructionHashResponse, ApiError> {
     debug!("/construction/hash");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
-    let signed_transaction = deserialize_signed_transaction(&request.signed_transaction);
+    let signed_transaction = deserialize_signed_transaction(&request.signed_transaction)?;
 
     Ok(ConstructionHashResponse {
         transaction_identifier: TransactionIdentifier {
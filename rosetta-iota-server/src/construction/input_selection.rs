@@ -0,0 +1,86 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    construction::add_amounts,
+    consts::{DUST_THRESHOLD, MAX_TRANSACTION_INPUTS},
+    error::ApiError,
+    types::InputSelectionStrategy,
+};
+
+/// An unspent output of the sender that can be used as input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnspentCoin {
+    pub output_id: String,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Selection {
+    pub coins: Vec<UnspentCoin>,
+    pub change: u64,
+}
+
+/// Selects coins that cover `amount`. The change must not become a dust output, so coins are added until it is either
+/// zero or at least `DUST_THRESHOLD`.
+///
+/// `LargestFirst` spends as few coins as possible. `DustConsolidating` additionally spends every dust coin of the
+/// sender, which frees the dust allowance they occupy.
+pub fn select_inputs(
+    mut coins: Vec<UnspentCoin>,
+    amount: u64,
+    strategy: InputSelectionStrategy,
+) -> Result<Selection, ApiError> {
+    coins.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.output_id.cmp(&b.output_id)));
+
+    let (mut selected, remaining): (Vec<UnspentCoin>, Vec<UnspentCoin>) = match strategy {
+        InputSelectionStrategy::LargestFirst => (Vec::new(), coins),
+        InputSelectionStrategy::DustConsolidating => {
+            let (mut dust, remaining): (Vec<UnspentCoin>, Vec<UnspentCoin>) =
+                coins.into_iter().partition(|coin| coin.amount < DUST_THRESHOLD);
+            // leave room for at least one regular coin
+            dust.truncate(MAX_TRANSACTION_INPUTS - 1);
+            (dust, remaining)
+        }
+    };
+
+    let mut total = selected
+        .iter()
+        .try_fold(0, |total, coin| add_amounts(total, coin.amount))?;
+    let mut remaining = remaining.into_iter();
+    while !covers(total, amount)? {
+        if selected.len() == MAX_TRANSACTION_INPUTS {
+            return Err(ApiError::NonRetriable(format!(
+                "covering {} needs more than {} inputs",
+                amount, MAX_TRANSACTION_INPUTS
+            )));
+        }
+        match remaining.next() {
+            Some(coin) => {
+                total = add_amounts(total, coin.amount)?;
+                selected.push(coin);
+            }
+            None if total >= amount => {
+                return Err(ApiError::NonRetriable(format!(
+                    "the change of {} would be a dust output",
+                    total - amount
+                )));
+            }
+            None => {
+                return Err(ApiError::NonRetriable(format!(
+                    "insufficient funds: {} available, {} needed",
+                    total, amount
+                )));
+            }
+        }
+    }
+
+    Ok(Selection {
+        coins: selected,
+        change: total - amount,
+    })
+}
+
+fn covers(total: u64, amount: u64) -> Result<bool, ApiError> {
+    Ok(total == amount || total >= add_amounts(amount, DUST_THRESHOLD)?)
+}
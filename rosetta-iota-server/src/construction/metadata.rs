@@ -1,15 +1,28 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::ApiError, is_offline_mode_enabled, is_wrong_network, types::*, RosettaConfig};
+use crate::{
+    client::{get_output, get_unspent_outputs_of_address},
+    construction::{
+        dust::apply_output,
+        input_selection::{select_inputs, UnspentCoin},
+    },
+    data::block::block_handler::OutputCache,
+    error::ApiError,
+    is_offline_mode_enabled, is_wrong_network,
+    state::ServerState,
+    types::*,
+    RosettaConfig,
+};
 
 use bee_message::prelude::*;
+use bee_rest_api::types::responses::OutputResponse;
+use iota_client::Client;
 
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use crate::client::{build_client, get_output};
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::TryFrom};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -27,20 +40,19 @@ pub struct ConstructionMetadataResponse {
 pub async fn metadata(
     request: ConstructionMetadataRequest,
     rosetta_config: RosettaConfig,
+    server_state: ServerState,
 ) -> Result<ConstructionMetadataResponse, ApiError> {
     debug!("/construction/metadata");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     if is_offline_mode_enabled(&rosetta_config) {
-        return Err(ApiError::NonRetriable(
-            "endpoint is not available in offline mode".to_string(),
-        ));
+        return Err(ApiError::UnavailableOffline);
     }
 
-    let client = build_client(&rosetta_config).await?;
+    let client = server_state.client_pool.get().await?;
 
     let mut utxo_inputs_metadata = HashMap::new();
     for output_id_string in request.options.utxo_inputs {
@@ -50,10 +62,140 @@ pub async fn metadata(
 
         let output = get_output(output_id, &client).await?;
 
+        if output.is_spent {
+            return Err(ApiError::OutputAlreadySpent(format!(
+                "output {} can not be used as input",
+                output_id_string
+            )));
+        }
+
         utxo_inputs_metadata.insert(output_id_string, output);
     }
 
+    let selected_inputs = match request.options.input_selection {
+        Some(input_selection) => {
+            let (selected_inputs, selected_outputs) = selected_inputs(input_selection, &client).await?;
+            utxo_inputs_metadata.extend(selected_outputs);
+            Some(selected_inputs)
+        }
+        None => None,
+    };
+
+    // besides the recipients of dust outputs, the owners of spent dust allowance outputs can end up with too many dust
+    // outputs
+    let mut dust_addresses = request.options.dust_addresses;
+    for output_response in utxo_inputs_metadata.values() {
+        let output = Output::try_from(&output_response.output)
+            .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
+        if let Output::SignatureLockedDustAllowance(output) = output {
+            let address = output.address().to_bech32(&rosetta_config.bech32_hrp);
+            if !dust_addresses.contains(&address) {
+                dust_addresses.push(address);
+            }
+        }
+    }
+
+    let mut dust_allowances = HashMap::new();
+    for address in dust_addresses {
+        Address::try_from_bech32(&address)
+            .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", address, e)))?;
+        let dust_allowance_state = dust_allowance_state(&address, &client).await?;
+        dust_allowances.insert(address, dust_allowance_state);
+    }
+
     Ok(ConstructionMetadataResponse {
-        metadata: ConstructionMetadata { utxo_inputs_metadata },
+        metadata: ConstructionMetadata {
+            utxo_inputs_metadata,
+            selected_inputs,
+            dust_allowances,
+            indexation: request.options.indexation,
+        },
     })
 }
+
+async fn selected_inputs(
+    input_selection: InputSelectionOptions,
+    iota_client: &Client,
+) -> Result<(SelectedInputs, HashMap<String, OutputResponse>), ApiError> {
+    // dust allowance outputs stay untouched, spending them could invalidate the dust outputs of the sender
+    let mut coins = Vec::new();
+    let mut candidates = HashMap::new();
+    for (output_id, output, output_response) in unspent_outputs(&input_selection.sender.address, iota_client).await? {
+        if let Output::SignatureLockedSingle(output) = output {
+            coins.push(UnspentCoin {
+                output_id: output_id.to_string(),
+                amount: output.amount(),
+            });
+            candidates.insert(output_id.to_string(), output_response);
+        }
+    }
+
+    let selection = select_inputs(coins, input_selection.amount, input_selection.strategy)?;
+
+    let selected_outputs = selection
+        .coins
+        .iter()
+        .filter_map(|coin| {
+            candidates
+                .remove(&coin.output_id)
+                .map(|output_response| (coin.output_id.clone(), output_response))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let selected_inputs = SelectedInputs {
+        sender: input_selection.sender,
+        coins: selection
+            .coins
+            .into_iter()
+            .map(|coin| CoinIdentifier {
+                identifier: coin.output_id,
+            })
+            .collect(),
+        change: selection.change,
+    };
+
+    Ok((selected_inputs, selected_outputs))
+}
+
+async fn dust_allowance_state(bech32_addr: &str, iota_client: &Client) -> Result<DustAllowanceState, ApiError> {
+    let mut dust_allowance_state = DustAllowanceState::default();
+    for (_, output, _) in unspent_outputs(bech32_addr, iota_client).await? {
+        apply_output(&mut dust_allowance_state, &output, true);
+    }
+    Ok(dust_allowance_state)
+}
+
+/// Fetches the unspent outputs of the address.
+async fn unspent_outputs(
+    bech32_addr: &str,
+    iota_client: &Client,
+) -> Result<Vec<(OutputId, Output, OutputResponse)>, ApiError> {
+    let output_ids = get_unspent_outputs_of_address(bech32_addr, iota_client)
+        .await?
+        .output_ids
+        .iter()
+        .map(|output_id| {
+            output_id
+                .parse::<OutputId>()
+                .map_err(|e| ApiError::NonRetriable(format!("can not parse output id: {}", e)))
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    let mut output_cache = OutputCache::new();
+    output_cache
+        .fetch(output_ids.iter().copied(), false, iota_client)
+        .await?;
+
+    let mut unspent_outputs = Vec::new();
+    for output_id in output_ids {
+        let output_response = match output_cache.get(&output_id) {
+            Some(output_response) if !output_response.is_spent => output_response.clone(),
+            _ => continue,
+        };
+        let output = Output::try_from(&output_response.output)
+            .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
+        unspent_outputs.push((output_id, output, output_response));
+    }
+
+    Ok(unspent_outputs)
+}
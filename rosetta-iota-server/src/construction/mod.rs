@@ -6,72 +6,87 @@ use crate::{
         combine::combine, derive::derive, hash::hash, metadata::metadata, parse::parse, payloads::payloads,
         preprocess::preprocess, submit::submit,
     },
-    filters::{handle, with_rosetta_config},
-    types::{SignedTransaction, UnsignedTransaction},
+    error::ApiError,
+    filters::{handle, with_rosetta_config, with_server_state},
+    state::ServerState,
+    types::IndexationMetadata,
     RosettaConfig,
 };
 
+use bee_message::prelude::IndexationPayload;
+
 use warp::Filter;
 
 pub mod combine;
 pub mod derive;
+pub mod dust;
+pub mod encoding;
 pub mod hash;
+pub mod input_selection;
 pub mod metadata;
 pub mod parse;
 pub mod payloads;
 pub mod preprocess;
 pub mod submit;
 
-pub fn routes(options: RosettaConfig) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+pub fn routes(
+    options: RosettaConfig,
+    server_state: ServerState,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::post()
         .and(
             warp::path!("construction" / "derive")
                 .and(warp::body::json())
                 .and(with_rosetta_config(options.clone()))
+                .and(with_server_state(server_state.clone()))
                 .and_then(handle(derive)),
         )
         .or(warp::path!("construction" / "preprocess")
             .and(warp::body::json())
             .and(with_rosetta_config(options.clone()))
+            .and(with_server_state(server_state.clone()))
             .and_then(handle(preprocess)))
         .or(warp::path!("construction" / "metadata")
             .and(warp::body::json())
             .and(with_rosetta_config(options.clone()))
+            .and(with_server_state(server_state.clone()))
             .and_then(handle(metadata)))
         .or(warp::path!("construction" / "payloads")
             .and(warp::body::json())
             .and(with_rosetta_config(options.clone()))
+            .and(with_server_state(server_state.clone()))
             .and_then(handle(payloads)))
         .or(warp::path!("construction" / "parse")
             .and(warp::body::json())
             .and(with_rosetta_config(options.clone()))
+            .and(with_server_state(server_state.clone()))
             .and_then(handle(parse)))
         .or(warp::path!("construction" / "combine")
             .and(warp::body::json())
             .and(with_rosetta_config(options.clone()))
+            .and(with_server_state(server_state.clone()))
             .and_then(handle(combine)))
         .or(warp::path!("construction" / "hash")
             .and(warp::body::json())
             .and(with_rosetta_config(options.clone()))
+            .and(with_server_state(server_state.clone()))
             .and_then(handle(hash)))
         .or(warp::path!("construction" / "submit")
             .and(warp::body::json())
             .and(with_rosetta_config(options))
+            .and(with_server_state(server_state))
             .and_then(handle(submit)))
 }
 
-fn serialize_unsigned_transaction(unsigned_transaction: &UnsignedTransaction) -> String {
-    hex::encode(serde_json::to_string(unsigned_transaction).unwrap())
-}
-
-fn deserialize_unsigned_transaction(string: &str) -> UnsignedTransaction {
-    serde_json::from_slice(&hex::decode(string).unwrap()).unwrap()
-}
-
-fn serialize_signed_transaction(signed_transaction: &SignedTransaction) -> String {
-    hex::encode(serde_json::to_string(signed_transaction).unwrap())
+fn indexation_payload(indexation: &IndexationMetadata) -> Result<IndexationPayload, ApiError> {
+    let index = hex::decode(&indexation.index)
+        .map_err(|e| ApiError::NonRetriable(format!("can not decode index of indexation: {}", e)))?;
+    let data = hex::decode(&indexation.data)
+        .map_err(|e| ApiError::NonRetriable(format!("can not decode data of indexation: {}", e)))?;
+    IndexationPayload::new(&index, &data).map_err(|e| ApiError::NonRetriable(format!("invalid indexation: {}", e)))
 }
 
-fn deserialize_signed_transaction(string: &str) -> SignedTransaction {
-    serde_json::from_slice(&hex::decode(string).unwrap()).unwrap()
+fn add_amounts(a: u64, b: u64) -> Result<u64, ApiError> {
+    a.checked_add(b)
+        .ok_or_else(|| ApiError::NonRetriable(format!("amount overflow: {} + {}", a, b)))
 }
@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    construction::{deserialize_signed_transaction, deserialize_unsigned_transaction},
+    construction::encoding::{deserialize_signed_transaction, deserialize_unsigned_transaction},
     error::ApiError,
     is_wrong_network,
     operations::{build_utxo_input_operation, build_utxo_output_operation},
+    state::ServerState,
     types::*,
     RosettaConfig,
 };
@@ -36,16 +37,25 @@ pub struct ConstructionParseRequest {
 pub struct ConstructionParseResponse {
     pub operations: Vec<Operation>,
     pub account_identifier_signers: Option<Vec<AccountIdentifier>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ConstructionParseMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConstructionParseMetadata {
+    pub indexation: IndexationMetadata,
 }
 
 pub async fn parse(
     request: ConstructionParseRequest,
     rosetta_config: RosettaConfig,
+    _server_state: ServerState,
 ) -> Result<ConstructionParseResponse, ApiError> {
     debug!("/construction/parse");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     if request.signed {
@@ -59,7 +69,7 @@ async fn parse_unsigned_transaction(
     construction_parse_request: ConstructionParseRequest,
     options: &RosettaConfig,
 ) -> Result<ConstructionParseResponse, ApiError> {
-    let unsigned_transaction = deserialize_unsigned_transaction(&construction_parse_request.transaction);
+    let unsigned_transaction = deserialize_unsigned_transaction(&construction_parse_request.transaction)?;
 
     let operations = essence_to_operations(
         unsigned_transaction.essence(),
@@ -71,6 +81,7 @@ async fn parse_unsigned_transaction(
     Ok(ConstructionParseResponse {
         operations,
         account_identifier_signers: None,
+        metadata: parse_metadata(unsigned_transaction.essence()),
     })
 }
 
@@ -78,7 +89,7 @@ async fn parse_signed_transaction(
     construction_parse_request: ConstructionParseRequest,
     options: &RosettaConfig,
 ) -> Result<ConstructionParseResponse, ApiError> {
-    let signed_transaction = deserialize_signed_transaction(&construction_parse_request.transaction);
+    let signed_transaction = deserialize_signed_transaction(&construction_parse_request.transaction)?;
 
     let transaction = signed_transaction.transaction();
 
@@ -101,6 +112,7 @@ async fn parse_signed_transaction(
     Ok(ConstructionParseResponse {
         operations,
         account_identifier_signers: Some(account_identifier_signers),
+        metadata: parse_metadata(transaction.essence()),
     })
 }
 
@@ -145,6 +157,16 @@ async fn essence_to_operations(
     Ok(operations)
 }
 
+fn parse_metadata(essence: &Essence) -> Option<ConstructionParseMetadata> {
+    let Essence::Regular(regular_essence) = essence;
+    match regular_essence.payload() {
+        Some(Payload::Indexation(indexation)) => Some(ConstructionParseMetadata {
+            indexation: IndexationMetadata::from(indexation.as_ref()),
+        }),
+        _ => None,
+    }
+}
+
 fn address_from_public_key(hex_string: &str) -> Result<Address, ApiError> {
     let public_key_bytes = hex::decode(hex_string)
         .map_err(|e| ApiError::NonRetriable(format!("can not derive address from public key: {}", e)))?;
@@ -1,7 +1,14 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{construction::serialize_unsigned_transaction, error::ApiError, is_wrong_network, types::*, RosettaConfig};
+use crate::{
+    construction::{dust::validate_dust, encoding::serialize_unsigned_transaction, indexation_payload},
+    error::ApiError,
+    is_wrong_network,
+    state::ServerState,
+    types::*,
+    RosettaConfig,
+};
 
 use bee_common::packable::Packable;
 use bee_message::prelude::*;
@@ -9,6 +16,8 @@ use bee_message::prelude::*;
 use log::debug;
 use serde::{Deserialize, Serialize};
 
+use std::{collections::HashMap, convert::TryFrom};
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ConstructionPayloadsRequest {
@@ -27,16 +36,17 @@ pub struct ConstructionPayloadsResponse {
 pub async fn payloads(
     request: ConstructionPayloadsRequest,
     rosetta_config: RosettaConfig,
+    _server_state: ServerState,
 ) -> Result<ConstructionPayloadsResponse, ApiError> {
     debug!("/construction/payloads");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     let mut inputs = vec![];
     let mut outputs = vec![];
-    let mut signing_payloads = vec![];
+    let mut signing_payloads: Vec<SigningPayload> = vec![];
 
     for operation in request.operations {
         let address = operation
@@ -60,32 +70,36 @@ pub async fn payloads(
             }
 
             "SIG_LOCKED_SINGLE_OUTPUT" => {
-                let address = Address::try_from_bech32(&address).unwrap();
+                let address = Address::try_from_bech32(&address)
+                    .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", address, e)))?;
 
                 let amount = operation
                     .amount
                     .ok_or_else(|| ApiError::NonRetriable("amount not populated".to_string()))?
                     .value
                     .parse::<u64>()
-                    .unwrap();
+                    .map_err(|e| ApiError::NonRetriable(format!("can not parse amount: {}", e)))?;
 
                 outputs.push(Output::SignatureLockedSingle(
-                    SignatureLockedSingleOutput::new(address, amount).unwrap(),
+                    SignatureLockedSingleOutput::new(address, amount)
+                        .map_err(|e| ApiError::NonRetriable(format!("can not build output: {}", e)))?,
                 ));
             }
 
             "SIG_LOCKED_DUST_ALLOWANCE_OUTPUT" => {
-                let address = Address::try_from_bech32(&address).unwrap();
+                let address = Address::try_from_bech32(&address)
+                    .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", address, e)))?;
 
                 let amount = operation
                     .amount
                     .ok_or_else(|| ApiError::NonRetriable("amount not populated".to_string()))?
                     .value
                     .parse::<u64>()
-                    .unwrap();
+                    .map_err(|e| ApiError::NonRetriable(format!("can not parse amount: {}", e)))?;
 
                 outputs.push(Output::SignatureLockedDustAllowance(
-                    SignatureLockedDustAllowanceOutput::new(address, amount).unwrap(),
+                    SignatureLockedDustAllowanceOutput::new(address, amount)
+                        .map_err(|e| ApiError::NonRetriable(format!("can not build output: {}", e)))?,
                 ));
             }
 
@@ -93,6 +107,34 @@ pub async fn payloads(
         }
     }
 
+    if let Some(selected_inputs) = &request.metadata.selected_inputs {
+        if !inputs.is_empty() {
+            return Err(ApiError::NonRetriable(
+                "inputs can not be listed when they were selected from a sender".to_string(),
+            ));
+        }
+
+        let sender = &selected_inputs.sender.address;
+        for coin in &selected_inputs.coins {
+            let utxo_input = coin
+                .identifier
+                .parse::<UtxoInput>()
+                .map_err(|e| ApiError::NonRetriable(e.to_string()))?;
+            inputs.push((Input::Utxo(utxo_input), sender.clone()));
+        }
+
+        if selected_inputs.change > 0 {
+            let address = Address::try_from_bech32(sender)
+                .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", sender, e)))?;
+            outputs.push(Output::SignatureLockedSingle(
+                SignatureLockedSingleOutput::new(address, selected_inputs.change)
+                    .map_err(|e| ApiError::NonRetriable(format!("can not build change output: {}", e)))?,
+            ));
+        }
+    }
+
+    validate_dust_outputs(&inputs, &outputs, &request.metadata, &rosetta_config)?;
+
     // sort inputs and outputs
     inputs.sort_unstable_by_key(|i| i.0.pack_new());
     outputs.sort_unstable_by_key(|o| o.pack_new());
@@ -107,11 +149,27 @@ pub async fn payloads(
         transaction_payload_essence = transaction_payload_essence.add_output(o);
     }
 
-    let essence = Essence::Regular(transaction_payload_essence.finish().unwrap());
+    if let Some(indexation) = &request.metadata.indexation {
+        transaction_payload_essence =
+            transaction_payload_essence.with_payload(Payload::Indexation(Box::new(indexation_payload(indexation)?)));
+    }
+
+    let essence = Essence::Regular(
+        transaction_payload_essence
+            .finish()
+            .map_err(|e| ApiError::NonRetriable(format!("can not build essence: {}", e)))?,
+    );
     let hash_to_sign = essence.hash();
     let unsigned_transaction = UnsignedTransaction::new(essence, request.metadata.utxo_inputs_metadata);
 
+    // all inputs sign the same essence hash, so one signature per address is enough
     for (_, address) in inputs {
+        if signing_payloads
+            .iter()
+            .any(|signing_payload| signing_payload.account_identifier.address == address)
+        {
+            continue;
+        }
         signing_payloads.push(SigningPayload {
             address: None,
             account_identifier: AccountIdentifier { address },
@@ -121,7 +179,38 @@ pub async fn payloads(
     }
 
     Ok(ConstructionPayloadsResponse {
-        unsigned_transaction: serialize_unsigned_transaction(&unsigned_transaction),
+        unsigned_transaction: serialize_unsigned_transaction(&unsigned_transaction)?,
         payloads: signing_payloads,
     })
 }
+
+fn validate_dust_outputs(
+    inputs: &[(Input, String)],
+    outputs: &[Output],
+    metadata: &ConstructionMetadata,
+    rosetta_config: &RosettaConfig,
+) -> Result<(), ApiError> {
+    let mut consumed = Vec::new();
+    for (input, _) in inputs {
+        let output_id = match input {
+            Input::Utxo(utxo_input) => utxo_input.output_id().to_string(),
+            _ => continue,
+        };
+        // without metadata of an input, the node will validate its effect on the dust allowance
+        if let Some(output_response) = metadata.utxo_inputs_metadata.get(&output_id) {
+            consumed.push(
+                Output::try_from(&output_response.output)
+                    .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?,
+            );
+        }
+    }
+
+    let mut dust_allowances = HashMap::new();
+    for (address, dust_allowance_state) in &metadata.dust_allowances {
+        let address = Address::try_from_bech32(address)
+            .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", address, e)))?;
+        dust_allowances.insert(address, *dust_allowance_state);
+    }
+
+    validate_dust(outputs, &consumed, &dust_allowances, &rosetta_config.bech32_hrp)
+}
@@ -1,7 +1,15 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::ApiError, is_wrong_network, types::*, RosettaConfig};
+use crate::{
+    construction::{add_amounts, indexation_payload},
+    consts::DUST_THRESHOLD,
+    error::ApiError,
+    is_wrong_network,
+    state::ServerState,
+    types::*,
+    RosettaConfig,
+};
 
 use bee_message::prelude::*;
 
@@ -13,6 +21,7 @@ use serde::{Deserialize, Serialize};
 pub struct ConstructionPreprocessRequest {
     pub network_identifier: NetworkIdentifier,
     pub operations: Vec<Operation>,
+    pub metadata: Option<PreprocessMetadata>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -24,14 +33,19 @@ pub struct ConstructionPreprocessResponse {
 pub async fn preprocess(
     request: ConstructionPreprocessRequest,
     rosetta_config: RosettaConfig,
+    _server_state: ServerState,
 ) -> Result<ConstructionPreprocessResponse, ApiError> {
     debug!("/construction/preprocess");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     let mut utxo_inputs = Vec::new();
+    let mut output_amount = 0;
+    // only recipients of dust outputs need their dust allowance state; the owners of spent dust allowance outputs are
+    // added by /construction/metadata, which knows the spent outputs
+    let mut dust_addresses = Vec::new();
     for operation in request.operations {
         match &operation.type_[..] {
             "INPUT" => {
@@ -47,11 +61,72 @@ pub async fn preprocess(
                     })?;
                 utxo_inputs.push(output_id.to_string());
             }
+            "SIG_LOCKED_SINGLE_OUTPUT" | "SIG_LOCKED_DUST_ALLOWANCE_OUTPUT" => {
+                let amount = operation
+                    .amount
+                    .ok_or_else(|| ApiError::NonRetriable("amount not populated".to_string()))?
+                    .value
+                    .parse::<u64>()
+                    .map_err(|e| ApiError::NonRetriable(format!("can not parse amount: {}", e)))?;
+                output_amount = add_amounts(output_amount, amount)?;
+
+                if operation.type_ == "SIG_LOCKED_SINGLE_OUTPUT" && amount < DUST_THRESHOLD {
+                    let address = operation
+                        .account
+                        .ok_or_else(|| ApiError::NonRetriable("account not populated".to_string()))?
+                        .address;
+                    if !dust_addresses.contains(&address) {
+                        dust_addresses.push(address);
+                    }
+                }
+            }
             _ => continue,
         }
     }
 
+    let metadata = request.metadata.unwrap_or(PreprocessMetadata {
+        sender: None,
+        input_selection_strategy: None,
+        indexation: None,
+    });
+
+    // without inputs, the inputs are selected from the coins of the sender by /construction/metadata
+    let input_selection = match metadata.sender {
+        Some(sender) => {
+            if !utxo_inputs.is_empty() {
+                return Err(ApiError::NonRetriable(
+                    "inputs can not be listed when they are selected from a sender".to_string(),
+                ));
+            }
+            Address::try_from_bech32(&sender.address)
+                .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", sender.address, e)))?;
+            if output_amount == 0 {
+                return Err(ApiError::NonRetriable("no outputs to select inputs for".to_string()));
+            }
+            Some(InputSelectionOptions {
+                sender,
+                amount: output_amount,
+                strategy: metadata.input_selection_strategy.unwrap_or_default(),
+            })
+        }
+        None if metadata.input_selection_strategy.is_some() => {
+            return Err(ApiError::NonRetriable(
+                "an input selection strategy needs a sender".to_string(),
+            ));
+        }
+        None => None,
+    };
+
+    if let Some(indexation) = &metadata.indexation {
+        indexation_payload(indexation)?;
+    }
+
     Ok(ConstructionPreprocessResponse {
-        options: PreprocessOptions { utxo_inputs },
+        options: PreprocessOptions {
+            utxo_inputs,
+            input_selection,
+            dust_addresses,
+            indexation: metadata.indexation,
+        },
     })
 }
@@ -2,13 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    construction::deserialize_signed_transaction, error::ApiError, is_offline_mode_enabled, is_wrong_network, types::*,
-    RosettaConfig,
+    client::node_error, construction::encoding::deserialize_signed_transaction, error::ApiError,
+    is_offline_mode_enabled, is_wrong_network, state::ServerState, types::*, RosettaConfig,
 };
 
 use bee_message::prelude::*;
 
-use crate::client::build_client;
 use log::debug;
 use serde::{Deserialize, Serialize};
 
@@ -29,29 +28,28 @@ pub struct ConstructionSubmitResponse {
 pub async fn submit(
     request: ConstructionSubmitRequest,
     rosetta_config: RosettaConfig,
+    server_state: ServerState,
 ) -> Result<ConstructionSubmitResponse, ApiError> {
     debug!("/construction/submit");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     if is_offline_mode_enabled(&rosetta_config) {
-        return Err(ApiError::NonRetriable(
-            "endpoint is not available in offline mode".to_string(),
-        ));
+        return Err(ApiError::UnavailableOffline);
     }
 
-    let client = build_client(&rosetta_config).await?;
+    let client = server_state.client_pool.get().await?;
 
-    let signed_transaction = deserialize_signed_transaction(&request.signed_transaction);
+    let signed_transaction = deserialize_signed_transaction(&request.signed_transaction)?;
     let transaction = signed_transaction.transaction();
 
     let message = client
         .message()
         .finish_message(Some(Payload::Transaction(Box::new(transaction.clone()))))
         .await
-        .map_err(|e| ApiError::NonRetriable(format!("can not build message: {}", e)))?;
+        .map_err(|e| node_error("can not build message", e))?;
 
     match client.post_message(&message).await {
         Ok(message_id) => Ok(ConstructionSubmitResponse {
@@ -63,6 +61,6 @@ pub async fn submit(
             },
         }),
 
-        Err(e) => Err(ApiError::NonRetriable(format!("can not submit message: {}", e))),
+        Err(e) => Err(node_error("can not submit message", e)),
     }
 }
@@ -3,12 +3,48 @@
 
 use crate::types::Currency;
 
+use std::time::Duration;
+
 pub const BLOCKCHAIN: &str = "iota";
 pub const ROSETTA_VERSION: &str = "1.4.10";
 pub const NODE_VERSION: &str = "1.0.5";
 
 pub const DUST_THRESHOLD: u64 = 1_000_000;
 
+// every DUST_ALLOWANCE_DIVISOR of dust allowance allows one dust output, up to MAX_DUST_OUTPUTS per address
+pub const DUST_ALLOWANCE_DIVISOR: u64 = 100_000;
+pub const MAX_DUST_OUTPUTS: u64 = 100;
+
+// upper bound of inputs of a transaction essence
+pub const MAX_TRANSACTION_INPUTS: usize = 127;
+
+// account that holds the funds of the legacy network which are not migrated yet
+pub const TREASURY_ACCOUNT: &str = "treasury";
+
+// blocks with more transactions only list their identifiers; clients fetch them through /block/transaction
+pub const MAX_BLOCK_TRANSACTIONS: usize = 100;
+
+// upper bound of messages in the past cone of a milestone that are walked to collect its conflicting transactions
+pub const MAX_PAST_CONE_MESSAGES: usize = 10_000;
+
+// upper bound of requests that are sent to a node at the same time while a block is built
+pub const MAX_CONCURRENT_NODE_REQUESTS: usize = 10;
+
+// upper bound of unreferenced messages that are walked to collect the mempool
+pub const MAX_MEMPOOL_MESSAGES: usize = 1000;
+
+// time for which the collected mempool is served before the Tangle is walked again
+pub const MEMPOOL_SNAPSHOT_TTL: Duration = Duration::from_secs(2);
+
+pub const NODE_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+pub const MILESTONE_FOLLOWER_INTERVAL: Duration = Duration::from_secs(5);
+
+// upper bound of blocks whose transactions are kept for /search/transactions, about a week of milestones
+pub const MAX_INDEXED_BLOCKS: usize = 60_480;
+
+pub const MAX_EVENTS_LIMIT: usize = 100;
+pub const MAX_SEARCH_LIMIT: usize = 100;
+
 pub fn iota_currency() -> Currency {
     Currency {
         symbol: String::from("IOTA"),
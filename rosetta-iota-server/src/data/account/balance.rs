@@ -2,15 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client::{build_client, get_balance_of_address},
+    client::{get_balance_of_address_from_pool, get_node_info, get_treasury},
     config::RosettaConfig,
-    consts::iota_currency,
+    consts::{iota_currency, TREASURY_ACCOUNT},
+    data::block::block_hashes::{block_identifier, resolve_block_identifier},
     error::ApiError,
     is_offline_mode_enabled, is_wrong_network,
-    types::{AccountIdentifier, Amount, BlockIdentifier, Currency, NetworkIdentifier},
+    state::ServerState,
+    types::{AccountIdentifier, Amount, BlockIdentifier, Currency, NetworkIdentifier, PartialBlockIdentifier},
 };
 
-use bee_message::milestone::MilestoneIndex;
+use bee_message::{address::Address, milestone::MilestoneIndex};
+use iota_client::Client;
 
 use log::debug;
 use serde::{Deserialize, Serialize};
@@ -21,6 +24,8 @@ pub struct AccountBalanceRequest {
     pub network_identifier: NetworkIdentifier,
     pub account_identifier: AccountIdentifier,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_identifier: Option<PartialBlockIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub currencies: Option<Vec<Currency>>,
 }
 
@@ -34,17 +39,16 @@ pub struct AccountBalanceResponse {
 pub async fn account_balance(
     request: AccountBalanceRequest,
     rosetta_config: RosettaConfig,
+    server_state: ServerState,
 ) -> Result<AccountBalanceResponse, ApiError> {
     debug!("/account/balance");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     if is_offline_mode_enabled(&rosetta_config) {
-        return Err(ApiError::NonRetriable(
-            "endpoint does not support offline mode".to_string(),
-        ));
+        return Err(ApiError::UnavailableOffline);
     }
 
     // only support IOTA currency
@@ -58,25 +62,86 @@ pub async fn account_balance(
         }
     }
 
-    let (amount, ledger_index) =
-        address_balance_with_ledger_index(&request.account_identifier.address, &rosetta_config).await?;
+    let client = server_state.client_pool.get().await?;
+
+    if request.account_identifier.address == TREASURY_ACCOUNT {
+        if request.block_identifier.is_some() {
+            return Err(ApiError::NonRetriable(
+                "historical balances of the treasury are not supported".to_string(),
+            ));
+        }
+        let (amount, block_identifier) = treasury_balance(&server_state, &client).await?;
+        return Ok(AccountBalanceResponse {
+            block_identifier,
+            balances: vec![amount],
+        });
+    }
+
+    let address = Address::try_from_bech32(&request.account_identifier.address).map_err(|e| {
+        ApiError::InvalidAddress(format!("can not parse {}: {}", request.account_identifier.address, e))
+    })?;
+
+    let (amount, block_identifier) = match request.block_identifier {
+        Some(partial_block_identifier) => {
+            historical_address_balance(&address, &partial_block_identifier, &server_state, &client).await?
+        }
+        None => {
+            let (amount, ledger_index) =
+                address_balance_with_ledger_index(&request.account_identifier.address, &server_state).await?;
+            (amount, block_identifier(*ledger_index, &server_state, &client).await?)
+        }
+    };
 
     Ok(AccountBalanceResponse {
-        block_identifier: BlockIdentifier {
-            index: *ledger_index,
-            hash: (*ledger_index).to_string(),
-        },
+        block_identifier,
         balances: vec![amount],
     })
 }
 
+async fn historical_address_balance(
+    address: &Address,
+    partial_block_identifier: &PartialBlockIdentifier,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<(Amount, BlockIdentifier), ApiError> {
+    let balance_store = server_state
+        .balance_store
+        .as_ref()
+        .ok_or_else(|| ApiError::NonRetriable("historical balance lookup is not enabled".to_string()))?;
+
+    let block_identifier = resolve_block_identifier(partial_block_identifier, server_state, iota_client).await?;
+
+    let amount = Amount {
+        value: balance_store.balance(address, block_identifier.index)?.to_string(),
+        currency: iota_currency(),
+    };
+
+    Ok((amount, block_identifier))
+}
+
+async fn treasury_balance(
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<(Amount, BlockIdentifier), ApiError> {
+    let confirmed_milestone_index = get_node_info(iota_client).await?.confirmed_milestone_index;
+    let treasury = get_treasury(iota_client).await?;
+
+    let amount = Amount {
+        value: treasury.amount.to_string(),
+        currency: iota_currency(),
+    };
+
+    Ok((
+        amount,
+        block_identifier(confirmed_milestone_index, server_state, iota_client).await?,
+    ))
+}
+
 async fn address_balance_with_ledger_index(
     address: &str,
-    options: &RosettaConfig,
+    server_state: &ServerState,
 ) -> Result<(Amount, MilestoneIndex), ApiError> {
-    let client = build_client(options).await?;
-
-    let balance_response = get_balance_of_address(address, &client).await?;
+    let balance_response = get_balance_of_address_from_pool(address, &server_state.client_pool).await?;
 
     let amount = Amount {
         value: balance_response.balance.to_string(),
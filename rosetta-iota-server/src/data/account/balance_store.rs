@@ -0,0 +1,277 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{consts::TREASURY_ACCOUNT, error::ApiError, types::AccountIdentifier};
+
+use bee_common::packable::Packable;
+use bee_message::address::Address;
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+pub const SEP_INDEX_FILE: &str = "sep_index";
+pub const BOOTSTRAP_BALANCES_FILE: &str = "bootstrap_balances.json";
+// written by the server next to the snapshot files, one line with the balance diffs of every applied milestone
+pub const BALANCE_DIFFS_FILE: &str = "balance_diffs.jsonl";
+
+// entry of the bootstrap_balances.json file written by rosetta-iota-snapshot
+#[derive(Deserialize)]
+struct BootstrapBalanceEntry {
+    account_identifier: AccountIdentifier,
+    value: String,
+}
+
+// line of the balance_diffs.jsonl file; addresses are hex encoded in their packed form
+#[derive(Deserialize, Serialize)]
+struct BalanceDiffsEntry {
+    milestone_index: u32,
+    diffs: HashMap<String, i64>,
+}
+
+/// Balances of all addresses from the snapshot's sep_index onward. Starting from the snapshot balances, the balance
+/// diffs of every applied milestone are recorded per address, so the balance at any applied milestone can be looked
+/// up. If the store was loaded from a snapshot directory, the applied diffs are appended to a file there and replayed
+/// on the next start.
+#[derive(Clone)]
+pub struct BalanceStore {
+    inner: Arc<RwLock<BalanceHistory>>,
+    diffs_file: Option<PathBuf>,
+}
+
+struct BalanceHistory {
+    sep_index: u32,
+    ledger_index: u32,
+    // balance of an address after each milestone that changed it
+    balances: HashMap<Address, Vec<(u32, u64)>>,
+}
+
+impl BalanceStore {
+    pub fn new(sep_index: u32, bootstrap_balances: HashMap<Address, u64>) -> Self {
+        let balances = bootstrap_balances
+            .into_iter()
+            .map(|(address, balance)| (address, vec![(sep_index, balance)]))
+            .collect();
+
+        Self {
+            inner: Arc::new(RwLock::new(BalanceHistory {
+                sep_index,
+                ledger_index: sep_index,
+                balances,
+            })),
+            diffs_file: None,
+        }
+    }
+
+    /// Loads the files that rosetta-iota-snapshot wrote to the given directory and replays the balance diffs that were
+    /// applied before.
+    pub fn from_snapshot_dir(snapshot_dir: &Path) -> Result<Self, String> {
+        let sep_index = fs::read_to_string(snapshot_dir.join(SEP_INDEX_FILE))
+            .map_err(|e| format!("can not read {} file: {}", SEP_INDEX_FILE, e))?
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| format!("can not parse sep index: {}", e))?;
+
+        let bootstrap_entries: Vec<BootstrapBalanceEntry> = serde_json::from_str(
+            &fs::read_to_string(snapshot_dir.join(BOOTSTRAP_BALANCES_FILE))
+                .map_err(|e| format!("can not read {} file: {}", BOOTSTRAP_BALANCES_FILE, e))?,
+        )
+        .map_err(|e| format!("can not parse {} file: {}", BOOTSTRAP_BALANCES_FILE, e))?;
+
+        let mut bootstrap_balances = HashMap::new();
+        for entry in bootstrap_entries {
+            // the treasury is not an address, its balance is only served for the current ledger state
+            if entry.account_identifier.address == TREASURY_ACCOUNT {
+                continue;
+            }
+            let address = Address::try_from_bech32(&entry.account_identifier.address)
+                .map_err(|e| format!("can not parse {}: {}", entry.account_identifier.address, e))?;
+            let balance = entry
+                .value
+                .parse::<u64>()
+                .map_err(|e| format!("can not parse balance of {}: {}", entry.account_identifier.address, e))?;
+            bootstrap_balances.insert(address, balance);
+        }
+
+        let mut balance_store = Self::new(sep_index, bootstrap_balances);
+        balance_store.replay_diffs(&snapshot_dir.join(BALANCE_DIFFS_FILE))?;
+        balance_store.diffs_file = Some(snapshot_dir.join(BALANCE_DIFFS_FILE));
+
+        Ok(balance_store)
+    }
+
+    fn replay_diffs(&self, diffs_file: &Path) -> Result<(), String> {
+        let diffs = match fs::read_to_string(diffs_file) {
+            Ok(diffs) => diffs,
+            Err(_) => return Ok(()),
+        };
+
+        let mut replayed_len = 0;
+        for line in diffs.split_inclusive('\n') {
+            // the last line is incomplete if the server stopped while writing it; it is cut off and its milestone is
+            // applied again
+            let entry: BalanceDiffsEntry = match serde_json::from_str(line) {
+                Ok(entry) if line.ends_with('\n') => entry,
+                _ => break,
+            };
+
+            let mut balance_diffs = HashMap::new();
+            for (address, diff) in entry.diffs {
+                let address = hex::decode(&address)
+                    .ok()
+                    .and_then(|bytes| Address::unpack(&mut bytes.as_slice()).ok())
+                    .ok_or_else(|| format!("can not parse address {} in {} file", address, BALANCE_DIFFS_FILE))?;
+                balance_diffs.insert(address, diff);
+            }
+
+            self.apply(entry.milestone_index, balance_diffs, None)
+                .map_err(|e| format!("can not replay {} file: {:?}", BALANCE_DIFFS_FILE, e))?;
+            replayed_len += line.len();
+        }
+
+        if replayed_len < diffs.len() {
+            OpenOptions::new()
+                .write(true)
+                .open(diffs_file)
+                .and_then(|file| file.set_len(replayed_len as u64))
+                .map_err(|e| format!("can not truncate {} file: {}", BALANCE_DIFFS_FILE, e))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn sep_index(&self) -> u32 {
+        self.inner.read().unwrap().sep_index
+    }
+
+    /// Index of the last milestone whose balance diffs were applied.
+    pub fn ledger_index(&self) -> u32 {
+        self.inner.read().unwrap().ledger_index
+    }
+
+    /// Applies the balance diffs of the milestone following the current ledger index and records them in the diffs file,
+    /// if there is one.
+    pub fn apply_milestone(&self, milestone_index: u32, balance_diffs: HashMap<Address, i64>) -> Result<(), ApiError> {
+        self.apply(milestone_index, balance_diffs, self.diffs_file.as_deref())
+    }
+
+    fn apply(
+        &self,
+        milestone_index: u32,
+        balance_diffs: HashMap<Address, i64>,
+        diffs_file: Option<&Path>,
+    ) -> Result<(), ApiError> {
+        let mut history = self.inner.write().unwrap();
+
+        if milestone_index != history.ledger_index + 1 {
+            return Err(ApiError::NonRetriable(format!(
+                "can not apply balance diffs of milestone {} on top of milestone {}",
+                milestone_index, history.ledger_index
+            )));
+        }
+
+        // the new balances are computed before anything is stored, so diffs that do not fit the history leave it
+        // untouched
+        let mut new_balances = Vec::new();
+        for (address, diff) in &balance_diffs {
+            if *diff == 0 {
+                continue;
+            }
+            let previous_balance = history
+                .balances
+                .get(address)
+                .and_then(|balances| balances.last())
+                .map_or(0, |(_, balance)| *balance);
+            let balance = i64::try_from(previous_balance)
+                .ok()
+                .and_then(|previous_balance| previous_balance.checked_add(*diff))
+                .and_then(|balance| u64::try_from(balance).ok())
+                .ok_or_else(|| {
+                    ApiError::NonRetriable(format!(
+                        "can not apply balance diff {} of milestone {} to balance {}",
+                        diff, milestone_index, previous_balance
+                    ))
+                })?;
+            new_balances.push((*address, balance));
+        }
+
+        if let Some(diffs_file) = diffs_file {
+            append_diffs(diffs_file, milestone_index, &balance_diffs)?;
+        }
+
+        for (address, balance) in new_balances {
+            history
+                .balances
+                .entry(address)
+                .or_insert_with(Vec::new)
+                .push((milestone_index, balance));
+        }
+
+        history.ledger_index = milestone_index;
+
+        Ok(())
+    }
+
+    pub fn balance(&self, address: &Address, milestone_index: u32) -> Result<u64, ApiError> {
+        let history = self.inner.read().unwrap();
+
+        if milestone_index < history.sep_index {
+            return Err(ApiError::NonRetriable(format!(
+                "balances are only available from milestone {} onward",
+                history.sep_index
+            )));
+        }
+        if milestone_index > history.ledger_index {
+            return Err(ApiError::Retriable(format!(
+                "balances of milestone {} not available yet",
+                milestone_index
+            )));
+        }
+
+        let balance = match history.balances.get(address) {
+            Some(balances) => {
+                // number of changes up to the requested milestone
+                let changes = balances.partition_point(|(index, _)| *index <= milestone_index);
+                if changes == 0 {
+                    0
+                } else {
+                    balances[changes - 1].1
+                }
+            }
+            None => 0,
+        };
+
+        Ok(balance)
+    }
+}
+
+fn append_diffs(
+    diffs_file: &Path,
+    milestone_index: u32,
+    balance_diffs: &HashMap<Address, i64>,
+) -> Result<(), ApiError> {
+    let entry = BalanceDiffsEntry {
+        milestone_index,
+        diffs: balance_diffs
+            .iter()
+            .map(|(address, diff)| (hex::encode(address.pack_new()), *diff))
+            .collect(),
+    };
+    let mut line = serde_json::to_string(&entry)
+        .map_err(|e| ApiError::NonRetriable(format!("can not serialize balance diffs: {}", e)))?;
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(diffs_file)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| ApiError::NonRetriable(format!("can not write {} file: {}", BALANCE_DIFFS_FILE, e)))
+}
@@ -2,26 +2,32 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client::{build_client, get_output, get_unspent_outputs_of_address},
+    client::{get_output, get_unspent_outputs_of_address},
     config::RosettaConfig,
     consts::iota_currency,
+    data::block::block_hashes::block_identifier,
     error::ApiError,
     is_offline_mode_enabled, is_wrong_network,
+    state::ServerState,
     types::{AccountIdentifier, NetworkIdentifier, *},
 };
 
-use bee_message::{milestone::MilestoneIndex, output::OutputId};
+use bee_message::{
+    address::Address,
+    input::Input,
+    milestone::MilestoneIndex,
+    output::{Output, OutputId},
+    payload::transaction::Essence,
+};
 use bee_rest_api::types::{
     dtos::{AddressDto, OutputDto},
     responses::{OutputResponse, OutputsAddressResponse},
 };
 
-use iota_client::Client;
-
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -36,30 +42,37 @@ pub struct AccountCoinsRequest {
 pub struct AccountCoinsResponse {
     pub block_identifier: BlockIdentifier,
     pub coins: Vec<Coin>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AccountCoinsResponseMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountCoinsResponseMetadata {
+    pub pending_spent_coins: Vec<Coin>,
 }
 
 pub async fn account_coins(
     request: AccountCoinsRequest,
     rosetta_config: RosettaConfig,
+    server_state: ServerState,
 ) -> Result<AccountCoinsResponse, ApiError> {
     debug!("/account/coins");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     if is_offline_mode_enabled(&rosetta_config) {
-        return Err(ApiError::NonRetriable(
-            "endpoint is not available in offline mode".to_string(),
-        ));
+        return Err(ApiError::UnavailableOffline);
     }
 
-    if request.include_mempool {
-        return Err(ApiError::NonRetriable("mempool coins are not supported".to_string()));
-    }
+    let address = Address::try_from_bech32(&request.account_identifier.address).map_err(|e| {
+        ApiError::InvalidAddress(format!("can not parse {}: {}", request.account_identifier.address, e))
+    })?;
 
     let (outputs, ledger_index) =
-        address_outputs_with_ledger_index(&request.account_identifier.address, &rosetta_config).await?;
+        address_outputs_with_ledger_index(&request.account_identifier.address, &server_state).await?;
 
     let mut coins = Vec::new();
     for (output_id, output_response) in outputs {
@@ -70,34 +83,106 @@ pub async fn account_coins(
             OutputDto::SignatureLockedDustAllowance(r) => match r.address {
                 AddressDto::Ed25519(_) => r.amount,
             },
-            _ => unimplemented!(),
+            _ => {
+                return Err(ApiError::UnsupportedOutputType(
+                    "only signature locked outputs are supported".to_string(),
+                ))
+            }
         };
 
-        coins.push(Coin {
-            coin_identifier: CoinIdentifier {
-                identifier: output_id.to_string(),
-            },
-            amount: Amount {
-                value: amount.to_string(),
-                currency: iota_currency(),
-            },
-        });
+        coins.push((output_id, build_coin(&output_id, amount, None)));
     }
 
+    let (coins, metadata) = if request.include_mempool {
+        let (coins, pending_spent_coins) = apply_pending_transactions(coins, &address, &server_state).await?;
+        (coins, Some(AccountCoinsResponseMetadata { pending_spent_coins }))
+    } else {
+        (coins.into_iter().map(|(_, coin)| coin).collect(), None)
+    };
+
+    let client = server_state.client_pool.get().await?;
+
     Ok(AccountCoinsResponse {
-        block_identifier: BlockIdentifier {
-            index: *ledger_index,
-            hash: (*ledger_index).to_string(),
-        },
+        block_identifier: block_identifier(*ledger_index, &server_state, &client).await?,
         coins,
+        metadata,
     })
 }
 
+fn build_coin(output_id: &OutputId, amount: u64, mempool_state: Option<MempoolState>) -> Coin {
+    Coin {
+        coin_identifier: CoinIdentifier {
+            identifier: output_id.to_string(),
+        },
+        amount: Amount {
+            value: amount.to_string(),
+            currency: iota_currency(),
+        },
+        metadata: mempool_state.map(|mempool_state| CoinMetadata { mempool_state }),
+    }
+}
+
+// removes the coins that pending transactions spend and adds the coins they create for the address; both are returned
+// marked with their mempool state
+async fn apply_pending_transactions(
+    confirmed_coins: Vec<(OutputId, Coin)>,
+    address: &Address,
+    server_state: &ServerState,
+) -> Result<(Vec<Coin>, Vec<Coin>), ApiError> {
+    let client = server_state.client_pool.get().await?;
+    let pending_transactions = server_state.mempool_snapshot.pending_transactions(&client).await?;
+
+    let mut pending_spent = HashSet::new();
+    for transaction in pending_transactions.values() {
+        let Essence::Regular(regular_essence) = transaction.essence();
+        for input in regular_essence.inputs() {
+            if let Input::Utxo(utxo_input) = input {
+                pending_spent.insert(*utxo_input.output_id());
+            }
+        }
+    }
+
+    let mut coins = Vec::new();
+    let mut pending_spent_coins = Vec::new();
+
+    for (output_id, mut coin) in confirmed_coins {
+        if pending_spent.contains(&output_id) {
+            coin.metadata = Some(CoinMetadata {
+                mempool_state: MempoolState::PendingSpend,
+            });
+            pending_spent_coins.push(coin);
+        } else {
+            coins.push(coin);
+        }
+    }
+
+    for (transaction_id, transaction) in pending_transactions.iter() {
+        let Essence::Regular(regular_essence) = transaction.essence();
+        for (output_index, output) in regular_essence.outputs().iter().enumerate() {
+            let (output_address, amount) = match output {
+                Output::SignatureLockedSingle(o) => (o.address(), o.amount()),
+                Output::SignatureLockedDustAllowance(o) => (o.address(), o.amount()),
+                _ => continue,
+            };
+
+            let output_id = OutputId::new(*transaction_id, output_index as u16)
+                .map_err(|e| ApiError::NonRetriable(format!("can not parse output id: {}", e)))?;
+
+            // outputs that are already spent by another pending transaction are no coins of the account anymore
+            if output_address == address && !pending_spent.contains(&output_id) {
+                coins.push(build_coin(&output_id, amount, Some(MempoolState::PendingCreation)));
+            }
+        }
+    }
+
+    Ok((coins, pending_spent_coins))
+}
+
 async fn address_outputs_with_ledger_index(
     address: &str,
-    options: &RosettaConfig,
+    server_state: &ServerState,
 ) -> Result<(HashMap<OutputId, OutputResponse>, MilestoneIndex), ApiError> {
-    let client: Client = build_client(options).await?;
+    let client = server_state.client_pool.get().await?;
 
     loop {
         let outputs_address_response: OutputsAddressResponse = get_unspent_outputs_of_address(address, &client).await?;
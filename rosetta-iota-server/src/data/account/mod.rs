@@ -4,24 +4,31 @@
 use crate::{
     config::RosettaConfig,
     data::account::{balance::account_balance, coins::account_coins},
-    filters::{handle, with_rosetta_config},
+    filters::{handle, with_rosetta_config, with_server_state},
+    state::ServerState,
 };
 
 use warp::Filter;
 
 pub mod balance;
+pub mod balance_store;
 pub mod coins;
 
-pub fn routes(options: RosettaConfig) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+pub fn routes(
+    options: RosettaConfig,
+    server_state: ServerState,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::post()
         .and(
             warp::path!("account" / "balance")
                 .and(warp::body::json())
                 .and(with_rosetta_config(options.clone()))
+                .and(with_server_state(server_state.clone()))
                 .and_then(handle(account_balance)),
         )
         .or(warp::path!("account" / "coins")
             .and(warp::body::json())
             .and(with_rosetta_config(options))
+            .and(with_server_state(server_state))
             .and_then(handle(account_coins)))
 }
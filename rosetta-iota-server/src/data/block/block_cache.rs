@@ -0,0 +1,94 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{data::block::block_handler::BlockResponse, error::ApiError};
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+/// Keeps built `/block` responses on disk, one JSON file per milestone index. Confirmed milestones never change, so
+/// cached blocks can still be served after the node pruned them.
+#[derive(Clone)]
+pub struct BlockCache {
+    dir: PathBuf,
+    indexes: Arc<RwLock<BTreeSet<u32>>>,
+}
+
+impl BlockCache {
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(dir).map_err(|e| format!("can not create block cache directory: {}", e))?;
+
+        let mut indexes = BTreeSet::new();
+        for entry in fs::read_dir(dir).map_err(|e| format!("can not read block cache directory: {}", e))? {
+            let path = entry
+                .map_err(|e| format!("can not read block cache directory: {}", e))?
+                .path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(milestone_index) = path
+                .file_stem()
+                .and_then(|file_stem| file_stem.to_str())
+                .and_then(|file_stem| file_stem.parse::<u32>().ok())
+            {
+                indexes.insert(milestone_index);
+            }
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            indexes: Arc::new(RwLock::new(indexes)),
+        })
+    }
+
+    pub fn contains(&self, milestone_index: u32) -> bool {
+        self.indexes.read().unwrap().contains(&milestone_index)
+    }
+
+    pub fn get(&self, milestone_index: u32) -> Result<Option<BlockResponse>, ApiError> {
+        if !self.contains(milestone_index) {
+            return Ok(None);
+        }
+
+        let json = fs::read_to_string(self.path(milestone_index))
+            .map_err(|e| ApiError::NonRetriable(format!("can not read cached block {}: {}", milestone_index, e)))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| ApiError::NonRetriable(format!("can not parse cached block {}: {}", milestone_index, e)))
+    }
+
+    pub fn insert(&self, milestone_index: u32, block_response: &BlockResponse) -> Result<(), ApiError> {
+        let json = serde_json::to_string(block_response)
+            .map_err(|e| ApiError::NonRetriable(format!("can not serialize block {}: {}", milestone_index, e)))?;
+
+        // the block is renamed into place, so a crash never leaves a partially written block behind
+        let tmp_path = self.dir.join(format!("{}.json.tmp", milestone_index));
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, self.path(milestone_index)))
+            .map_err(|e| ApiError::NonRetriable(format!("can not cache block {}: {}", milestone_index, e)))?;
+
+        self.indexes.write().unwrap().insert(milestone_index);
+        Ok(())
+    }
+
+    /// Returns the lowest milestone index from which on every block can be served, given that the node still has all
+    /// blocks from `first_node_index` on.
+    pub fn oldest_index(&self, first_node_index: u32) -> u32 {
+        let mut oldest_index = first_node_index;
+        for milestone_index in self.indexes.read().unwrap().range(..first_node_index).rev() {
+            if milestone_index + 1 != oldest_index {
+                break;
+            }
+            oldest_index = *milestone_index;
+        }
+        oldest_index
+    }
+
+    fn path(&self, milestone_index: u32) -> PathBuf {
+        self.dir.join(format!("{}.json", milestone_index))
+    }
+}
@@ -2,13 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client::{build_client, get_milestone, get_output, get_utxo_changes},
+    client::{get_message, get_message_metadata, get_output, get_pruning_index, get_utxo_changes_from_pool},
     config::RosettaConfig,
+    consts::{MAX_BLOCK_TRANSACTIONS, MAX_CONCURRENT_NODE_REQUESTS, MAX_PAST_CONE_MESSAGES},
+    data::block::block_hashes::{
+        block_identifier_unless_pruned, known_hash, milestone_with_hash, resolve_block_identifier,
+    },
     error::ApiError,
     is_offline_mode_enabled, is_wrong_network,
     operations::*,
+    state::ServerState,
     types::{
-        Block, BlockIdentifier, BlockTransaction, NetworkIdentifier, PartialBlockIdentifier, TransactionIdentifier,
+        Block, BlockIdentifier, BlockTransaction, BlockTransactionMetadata, IndexationMetadata, NetworkIdentifier,
+        PartialBlockIdentifier, TransactionIdentifier,
     },
 };
 
@@ -18,13 +24,15 @@ use bee_message::{
     Message,
 };
 
+use bee_rest_api::types::{dtos::LedgerInclusionStateDto, responses::OutputResponse};
+use futures::{stream, StreamExt, TryStreamExt};
 use iota_client::Client;
 
-use log::debug;
+use log::{debug, warn};
 use serde::{Deserialize, Serialize};
 
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{HashMap, HashSet},
     convert::TryFrom,
 };
 
@@ -41,134 +49,569 @@ pub struct BlockResponse {
     pub block: Block,
 }
 
-pub async fn block(request: BlockRequest, rosetta_config: RosettaConfig) -> Result<BlockResponse, ApiError> {
+pub async fn block(
+    request: BlockRequest,
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<BlockResponse, ApiError> {
     debug!("/block");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("request was made for wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     if is_offline_mode_enabled(&rosetta_config) {
-        return Err(ApiError::NonRetriable(
-            "endpoint does not support offline mode".to_string(),
-        ));
+        return Err(ApiError::UnavailableOffline);
     }
 
-    let milestone_index = match (request.block_identifier.index, request.block_identifier.hash) {
-        (Some(index), Some(hash)) => {
-            let hash = hash.parse::<u32>().map_err(|_| {
-                ApiError::NonRetriable("invalid block hash: can not parse milestone index from string".to_string())
-            })?;
-            if index != hash {
-                return Err(ApiError::NonRetriable(
-                    "block index does not relate to block hash".to_string(),
-                ));
-            } else {
-                index
-            }
-        }
-        (Some(index), None) => index,
-        (None, Some(hash)) => hash.parse::<u32>().map_err(|_| {
-            ApiError::NonRetriable("invalid block hash: can not parse milestone index from string".to_string())
-        })?,
-        (None, None) => {
+    // cached blocks are served without the node, they may have been pruned already
+    if let Some(block_response) = cached_block(&request.block_identifier, &server_state)? {
+        return Ok(block_response);
+    }
+
+    let client = server_state.client_pool.get().await?;
+
+    block_response(&request.block_identifier, &server_state, &rosetta_config, &client).await
+}
+
+fn cached_block(
+    partial_block_identifier: &PartialBlockIdentifier,
+    server_state: &ServerState,
+) -> Result<Option<BlockResponse>, ApiError> {
+    let block_cache = match &server_state.block_cache {
+        Some(block_cache) => block_cache,
+        None => return Ok(None),
+    };
+
+    let milestone_index = match known_milestone_index(partial_block_identifier, server_state) {
+        Some(milestone_index) => milestone_index,
+        None => return Ok(None),
+    };
+    check_not_before_genesis(milestone_index, server_state)?;
+
+    let block_response = match block_cache.get(milestone_index)? {
+        Some(block_response) => block_response,
+        None => return Ok(None),
+    };
+
+    if let Some(hash) = &partial_block_identifier.hash {
+        if hash != &block_response.block.block_identifier.hash {
             return Err(ApiError::NonRetriable(
-                "either block index or block hash must be set".to_string(),
+                "block index does not relate to block hash".to_string(),
             ));
         }
+    }
+    server_state
+        .block_hashes
+        .insert(milestone_index, block_response.block.block_identifier.hash.clone());
+
+    Ok(Some(block_response))
+}
+
+/// Returns the milestone index of a block if it is given or its hash is known locally.
+fn known_milestone_index(partial_block_identifier: &PartialBlockIdentifier, server_state: &ServerState) -> Option<u32> {
+    match (partial_block_identifier.index, &partial_block_identifier.hash) {
+        (Some(index), _) => Some(index),
+        (None, Some(hash)) => server_state.block_hashes.index(hash),
+        (None, None) => None,
+    }
+}
+
+/// Returns the block from the block cache, or builds it from the node and caches it.
+pub(crate) async fn block_response(
+    partial_block_identifier: &PartialBlockIdentifier,
+    server_state: &ServerState,
+    rosetta_config: &RosettaConfig,
+    client: &Client,
+) -> Result<BlockResponse, ApiError> {
+    if let Some(block_response) = cached_block(partial_block_identifier, server_state)? {
+        return Ok(block_response);
+    }
+
+    // blocks before the genesis and blocks the node pruned already are rejected before the node is asked for them;
+    // blocks that are only given by an unknown hash are checked once their index is resolved
+    let pruning_index = match known_milestone_index(partial_block_identifier, server_state) {
+        Some(milestone_index) => Some(check_available(milestone_index, server_state, client).await?),
+        None => None,
+    };
+    let block_identifier = resolve_block_identifier(partial_block_identifier, server_state, client).await?;
+    let pruning_index = match pruning_index {
+        Some(pruning_index) => pruning_index,
+        None => check_available(block_identifier.index, server_state, client).await?,
     };
 
-    let client = build_client(&rosetta_config).await?;
+    let milestone_index = block_identifier.index;
+
+    let mut output_cache = OutputCache::new();
+    let created_outputs = created_outputs_of_milestone(milestone_index, server_state).await?;
+    let messages = messages_of_created_outputs(created_outputs, &mut output_cache, client).await?;
+    let conflicting = conflicting_transactions(&block_identifier, client).await?;
+
+    // very busy milestones only list their transactions; they can be fetched one by one via /block/transaction
+    let (transactions, other_transactions) = if messages.len() + conflicting.len() > MAX_BLOCK_TRANSACTIONS {
+        let mut transaction_identifiers = messages
+            .iter()
+            .map(transaction_identifier)
+            .collect::<Result<Vec<_>, ApiError>>()?;
+        transaction_identifiers.extend(conflicting.iter().map(|conflicting_transaction| TransactionIdentifier {
+            hash: conflicting_transaction.payload.id().to_string(),
+        }));
+        (Vec::new(), Some(transaction_identifiers))
+    } else {
+        (
+            build_block_transactions(messages, conflicting, &mut output_cache, client, rosetta_config).await?,
+            None,
+        )
+    };
+
+    let timestamp = milestone_with_hash(milestone_index, server_state, client)
+        .await?
+        .timestamp
+        * 1000;
+
+    // the genesis block is its own parent
+    let parent_block_identifier = if milestone_index == server_state.genesis_index {
+        block_identifier.clone()
+    } else {
+        block_identifier_unless_pruned(milestone_index - 1, pruning_index, server_state, client).await?
+    };
 
     let block = Block {
-        block_identifier: BlockIdentifier {
-            index: milestone_index,
-            hash: milestone_index.to_string(),
-        },
-        parent_block_identifier: BlockIdentifier {
-            index: milestone_index - 1,
-            hash: (milestone_index - 1).to_string(),
-        },
-        timestamp: get_milestone(milestone_index, &client).await?.timestamp * 1000,
-        transactions: build_block_transactions(milestone_index, &client, &rosetta_config).await?,
+        block_identifier,
+        parent_block_identifier,
+        timestamp,
+        transactions,
+        other_transactions,
     };
 
-    Ok(BlockResponse { block })
+    let block_response = BlockResponse { block };
+
+    if let Some(block_cache) = &server_state.block_cache {
+        block_cache.insert(milestone_index, &block_response)?;
+    }
+
+    Ok(block_response)
+}
+
+pub(crate) fn check_not_before_genesis(milestone_index: u32, server_state: &ServerState) -> Result<(), ApiError> {
+    if milestone_index < server_state.genesis_index {
+        return Err(ApiError::BlockBeforeGenesis(format!(
+            "milestone {} is before the genesis milestone {}",
+            milestone_index, server_state.genesis_index
+        )));
+    }
+    Ok(())
 }
 
-async fn build_block_transactions(
+/// Checks that the block is neither before the genesis nor pruned by the node, and returns the pruning index.
+async fn check_available(milestone_index: u32, server_state: &ServerState, client: &Client) -> Result<u32, ApiError> {
+    check_not_before_genesis(milestone_index, server_state)?;
+    check_not_pruned(milestone_index, client).await
+}
+
+/// Returns the pruning index of the node if the milestone is still available.
+pub(crate) async fn check_not_pruned(milestone_index: u32, iota_client: &Client) -> Result<u32, ApiError> {
+    let pruning_index = get_pruning_index(iota_client).await?;
+    if milestone_index <= pruning_index {
+        return Err(ApiError::MilestonePruned(format!(
+            "milestone {} was pruned by the node",
+            milestone_index
+        )));
+    }
+    Ok(pruning_index)
+}
+
+/// Returns the oldest block that can be served if the blocks from `first_available_index` on are available. It is never
+/// before the genesis block or the configured oldest index, and the ID of its parent must be known, since pruned
+/// milestones can not be looked up anymore.
+pub(crate) fn oldest_block_index(
+    first_available_index: u32,
+    pruning_index: u32,
+    rosetta_config: &RosettaConfig,
+    server_state: &ServerState,
+) -> Result<u32, ApiError> {
+    let oldest_index = first_available_index
+        .max(server_state.genesis_index)
+        .max(rosetta_config.oldest_index.unwrap_or_default());
+
+    if oldest_index != server_state.genesis_index
+        && oldest_index - 1 <= pruning_index
+        && known_hash(oldest_index - 1, server_state)?.is_none()
+    {
+        return Ok(oldest_index + 1);
+    }
+    Ok(oldest_index)
+}
+
+pub(crate) async fn created_outputs_of_milestone(
     milestone_index: u32,
+    server_state: &ServerState,
+) -> Result<Vec<OutputId>, ApiError> {
+    get_utxo_changes_from_pool(milestone_index, &server_state.client_pool)
+        .await?
+        .created_outputs
+        .iter()
+        .map(|output_id| {
+            output_id
+                .parse::<OutputId>()
+                .map_err(|e| ApiError::NonRetriable(format!("can not parse output id: {}", e)))
+        })
+        .collect()
+}
+
+/// Outputs that were already fetched while a request is handled, so that every output is requested only once.
+pub(crate) struct OutputCache {
+    outputs: HashMap<OutputId, OutputResponse>,
+}
+
+impl OutputCache {
+    pub fn new() -> Self {
+        Self {
+            outputs: HashMap::new(),
+        }
+    }
+
+    /// Fetches the outputs that are not cached yet, several at a time. Outputs the node does not know are ignored if
+    /// `skip_missing` is set.
+    pub async fn fetch(
+        &mut self,
+        output_ids: impl IntoIterator<Item = OutputId>,
+        skip_missing: bool,
+        iota_client: &Client,
+    ) -> Result<(), ApiError> {
+        let missing = output_ids
+            .into_iter()
+            .filter(|output_id| !self.outputs.contains_key(output_id))
+            .collect::<HashSet<_>>();
+
+        let responses = stream::iter(missing)
+            .map(|output_id| async move { (output_id, get_output(output_id, iota_client).await) })
+            .buffer_unordered(MAX_CONCURRENT_NODE_REQUESTS)
+            .collect::<Vec<_>>()
+            .await;
+
+        for (output_id, response) in responses {
+            match response {
+                Ok(output_response) => {
+                    self.outputs.insert(output_id, output_response);
+                }
+                Err(ApiError::NonRetriable(_)) if skip_missing => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, output_id: &OutputId) -> Option<&OutputResponse> {
+        self.outputs.get(output_id)
+    }
+}
+
+fn utxo_inputs(transaction_payload: &TransactionPayload) -> Vec<OutputId> {
+    let Essence::Regular(regular_essence) = transaction_payload.essence();
+    regular_essence
+        .inputs()
+        .iter()
+        .filter_map(|input| match input {
+            Input::Utxo(utxo_input) => Some(*utxo_input.output_id()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) async fn build_block_transactions(
+    messages: Vec<MessageInfo>,
+    conflicting: Vec<ConflictingTransaction>,
+    output_cache: &mut OutputCache,
     iota_client: &Client,
     rosetta_config: &RosettaConfig,
 ) -> Result<Vec<BlockTransaction>, ApiError> {
-    let messages = messages_of_created_outputs(milestone_index, iota_client).await?;
+    // the inputs of all transactions are fetched at once
+    let inputs = messages
+        .iter()
+        .filter_map(|message_info| match message_info.message.payload() {
+            Some(Payload::Transaction(t)) => Some(utxo_inputs(t)),
+            _ => None,
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    output_cache.fetch(inputs, false, iota_client).await?;
+    let conflicting_inputs = conflicting
+        .iter()
+        .flat_map(|conflicting_transaction| utxo_inputs(&conflicting_transaction.payload))
+        .collect::<Vec<_>>();
+    output_cache.fetch(conflicting_inputs, true, iota_client).await?;
 
     let mut transactions = Vec::new();
 
-    for (_message_id, message_info) in messages {
-        let transaction = match message_info.message.payload() {
-            Some(Payload::Transaction(t)) => from_transaction(t, iota_client, rosetta_config).await?,
-            Some(Payload::Milestone(_)) => {
-                from_milestone(&message_info.created_outputs, iota_client, rosetta_config).await?
-            }
-            _ => return Err(ApiError::NonRetriable("unknown payload type in message".to_string())),
-        };
-        transactions.push(transaction);
+    for message_info in messages {
+        transactions.push(build_block_transaction(&message_info, output_cache, iota_client, rosetta_config).await?);
+    }
+
+    for conflicting_transaction in conflicting {
+        transactions.push(
+            build_conflicting_transaction(&conflicting_transaction, output_cache, iota_client, rosetta_config).await?,
+        );
     }
 
     Ok(transactions)
 }
 
-struct MessageInfo {
+pub(crate) async fn build_block_transaction(
+    message_info: &MessageInfo,
+    output_cache: &mut OutputCache,
+    iota_client: &Client,
+    rosetta_config: &RosettaConfig,
+) -> Result<BlockTransaction, ApiError> {
+    match message_info.message.payload() {
+        Some(Payload::Transaction(t)) => {
+            output_cache.fetch(utxo_inputs(t), false, iota_client).await?;
+            from_transaction(
+                &message_info.message_id,
+                &message_info.message,
+                t,
+                None,
+                output_cache,
+                rosetta_config,
+            )
+        }
+        Some(Payload::Milestone(m)) => {
+            output_cache
+                .fetch(message_info.created_outputs.iter().copied(), false, iota_client)
+                .await?;
+            from_milestone(
+                &message_info.message_id,
+                &message_info.message,
+                m,
+                &message_info.created_outputs,
+                output_cache,
+                rosetta_config,
+            )
+        }
+        _ => Err(ApiError::NonRetriable("unknown payload type in message".to_string())),
+    }
+}
+
+fn transaction_identifier(message_info: &MessageInfo) -> Result<TransactionIdentifier, ApiError> {
+    let hash = match message_info.message.payload() {
+        Some(Payload::Transaction(t)) => t.id().to_string(),
+        Some(Payload::Milestone(_)) => message_info
+            .created_outputs
+            .first()
+            .unwrap()
+            .transaction_id()
+            .to_string(),
+        _ => return Err(ApiError::NonRetriable("unknown payload type in message".to_string())),
+    };
+    Ok(TransactionIdentifier { hash })
+}
+
+pub(crate) struct MessageInfo {
+    pub message_id: MessageId,
     pub message: Message,
     pub created_outputs: Vec<OutputId>,
 }
 
-async fn messages_of_created_outputs(
-    milestone_index: u32,
+/// Groups the created outputs by the messages that created them. The messages are ordered by the ID of their
+/// transaction.
+pub(crate) async fn messages_of_created_outputs(
+    created_outputs: Vec<OutputId>,
+    output_cache: &mut OutputCache,
     iota_client: &Client,
-) -> Result<HashMap<MessageId, MessageInfo>, ApiError> {
-    let mut message_map = HashMap::new();
-
-    let created_outputs = get_utxo_changes(milestone_index, iota_client).await?.created_outputs;
-
-    for output_id_string in created_outputs {
-        let output_id = output_id_string
-            .parse::<OutputId>()
-            .map_err(|e| ApiError::NonRetriable(format!("can not parse output id: {}", e)))?;
+) -> Result<Vec<MessageInfo>, ApiError> {
+    output_cache
+        .fetch(created_outputs.iter().copied(), false, iota_client)
+        .await?;
 
-        let message_id = get_output(output_id, iota_client)
-            .await?
+    let mut created_outputs_by_message: HashMap<MessageId, Vec<OutputId>> = HashMap::new();
+    for output_id in created_outputs {
+        let message_id = output_cache
+            .get(&output_id)
+            .ok_or_else(|| ApiError::NonRetriable(format!("output {} not found", output_id)))?
             .message_id
             .parse::<MessageId>()
             .map_err(|e| ApiError::NonRetriable(format!("can not parse message id: {}", e)))?;
+        created_outputs_by_message
+            .entry(message_id)
+            .or_default()
+            .push(output_id);
+    }
 
-        match message_map.entry(message_id) {
-            Entry::Occupied(mut entry) => {
-                let message_info: &mut MessageInfo = entry.get_mut();
-                message_info.created_outputs.push(output_id);
-            }
-            Entry::Vacant(entry) => {
-                let message = iota_client
-                    .get_message()
-                    .data(&message_id)
-                    .await
-                    .map_err(|e| ApiError::NonRetriable(format!("can not get message: {}", e)))?;
-                let message_info = MessageInfo {
-                    message,
-                    created_outputs: vec![output_id],
+    let messages = stream::iter(created_outputs_by_message)
+        .map(|(message_id, mut created_outputs)| async move {
+            created_outputs.sort_by_key(|output_id| output_id.index());
+            get_message(&message_id, iota_client).await.map(|message| MessageInfo {
+                message_id,
+                message,
+                created_outputs,
+            })
+        })
+        .buffer_unordered(MAX_CONCURRENT_NODE_REQUESTS)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let mut messages = messages
+        .into_iter()
+        .map(|message_info| Ok((transaction_identifier(&message_info)?.hash, message_info)))
+        .collect::<Result<Vec<_>, ApiError>>()?;
+    messages.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(messages.into_iter().map(|(_, message_info)| message_info).collect())
+}
+
+pub(crate) async fn build_conflicting_transaction(
+    conflicting_transaction: &ConflictingTransaction,
+    output_cache: &mut OutputCache,
+    iota_client: &Client,
+    rosetta_config: &RosettaConfig,
+) -> Result<BlockTransaction, ApiError> {
+    output_cache
+        .fetch(utxo_inputs(&conflicting_transaction.payload), true, iota_client)
+        .await?;
+    from_transaction(
+        &conflicting_transaction.message_id,
+        &conflicting_transaction.message,
+        &conflicting_transaction.payload,
+        Some(conflicting_transaction.conflict_reason),
+        output_cache,
+        rosetta_config,
+    )
+}
+
+/// A transaction payload that was referenced by the milestone but not applied to the ledger.
+pub(crate) struct ConflictingTransaction {
+    pub message_id: MessageId,
+    pub message: Message,
+    pub payload: Box<TransactionPayload>,
+    pub conflict_reason: u8,
+}
+
+/// Collects the conflicting transactions of a milestone. The past cone of the milestone message is walked level by
+/// level, several messages at a time, as long as the messages were referenced by this milestone; messages referenced
+/// by other milestones end the walk, their past cone belongs to older blocks. The walk stops before a level that would
+/// exceed `MAX_PAST_CONE_MESSAGES` visited messages, so the same conflicting transactions are found on every walk.
+pub(crate) async fn conflicting_transactions(
+    block_identifier: &BlockIdentifier,
+    iota_client: &Client,
+) -> Result<Vec<ConflictingTransaction>, ApiError> {
+    let milestone_message_id = block_identifier
+        .hash
+        .parse::<MessageId>()
+        .map_err(|e| ApiError::NonRetriable(format!("can not parse milestone message id: {}", e)))?;
+    let milestone_index = block_identifier.index;
+
+    let mut conflicting = Vec::new();
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![milestone_message_id];
+
+    while !to_visit.is_empty() {
+        let unvisited = to_visit
+            .into_iter()
+            .filter(|message_id| !visited.contains(message_id))
+            .collect::<HashSet<_>>();
+
+        if visited.len() + unvisited.len() > MAX_PAST_CONE_MESSAGES {
+            warn!(
+                "past cone of milestone {} has more than {} messages, its conflicting transactions are not walked \
+                 completely",
+                milestone_index, MAX_PAST_CONE_MESSAGES
+            );
+            break;
+        }
+        visited.extend(unvisited.iter().copied());
+
+        let referenced_messages = stream::iter(unvisited)
+            .map(|message_id| async move {
+                let metadata = get_message_metadata(&message_id, iota_client).await?;
+                if metadata.referenced_by_milestone_index != Some(milestone_index) {
+                    return Ok(None);
+                }
+                // only conflicting messages are needed as a whole
+                let message = match metadata.ledger_inclusion_state {
+                    Some(LedgerInclusionStateDto::Conflicting) => Some(get_message(&message_id, iota_client).await?),
+                    _ => None,
                 };
-                entry.insert(message_info);
+                Ok::<_, ApiError>(Some((message_id, metadata, message)))
+            })
+            .buffer_unordered(MAX_CONCURRENT_NODE_REQUESTS)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        to_visit = Vec::new();
+        for (message_id, metadata, message) in referenced_messages.into_iter().flatten() {
+            if let Some(message) = message {
+                if let Some(Payload::Transaction(transaction)) = message.payload() {
+                    conflicting.push(ConflictingTransaction {
+                        message_id,
+                        payload: transaction.clone(),
+                        message,
+                        conflict_reason: metadata.conflict_reason.unwrap_or(255),
+                    });
+                }
+            }
+
+            for parent in metadata.parent_message_ids {
+                to_visit.push(
+                    parent
+                        .parse::<MessageId>()
+                        .map_err(|e| ApiError::NonRetriable(format!("can not parse message id: {}", e)))?,
+                );
             }
         }
     }
 
-    Ok(message_map)
+    // the walk finishes in any order
+    conflicting.sort_by_key(|conflicting_transaction| conflicting_transaction.payload.id());
+
+    Ok(conflicting)
+}
+
+fn conflict_reason_description(conflict_reason: u8) -> String {
+    match conflict_reason {
+        1 => "input UTXO already spent",
+        2 => "input UTXO already spent by a transaction in the same milestone",
+        3 => "input UTXO not found",
+        4 => "sum of input and output amounts does not match",
+        5 => "invalid signature",
+        6 => "invalid dust allowance",
+        _ => "semantic validation failed",
+    }
+    .to_string()
 }
 
-async fn from_transaction(
+/// Describes the message that contains the transaction; the index of an attached indexation payload is commonly used
+/// as a memo.
+fn transaction_metadata(
+    message_id: &MessageId,
+    message: &Message,
+    conflict_reason: Option<u8>,
+) -> BlockTransactionMetadata {
+    let indexation = match message.payload() {
+        Some(Payload::Transaction(transaction)) => {
+            let Essence::Regular(regular_essence) = transaction.essence();
+            match regular_essence.payload() {
+                Some(Payload::Indexation(indexation)) => Some(IndexationMetadata::from(indexation.as_ref())),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    BlockTransactionMetadata {
+        conflict_reason: conflict_reason.map(conflict_reason_description),
+        message_id: Some(message_id.to_string()),
+        parents: Some(message.parents().iter().map(|parent| parent.to_string()).collect()),
+        indexation,
+    }
+}
+
+fn from_transaction(
+    message_id: &MessageId,
+    message: &Message,
     transaction_payload: &TransactionPayload,
-    iota_client: &Client,
+    conflict_reason: Option<u8>,
+    output_cache: &OutputCache,
     rosetta_config: &RosettaConfig,
 ) -> Result<BlockTransaction, ApiError> {
     let Essence::Regular(regular_essence) = transaction_payload.essence();
@@ -181,14 +624,19 @@ async fn from_transaction(
             _ => return Err(ApiError::NonRetriable("unknown UTXO type".to_string())),
         };
 
-        let output = Output::try_from(
-            &iota_client
-                .get_output(utxo_input)
-                .await
-                .map_err(|e| ApiError::NonRetriable(format!("can not get output: {}", e)))?
-                .output,
-        )
-        .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
+        let output_response = match output_cache.get(utxo_input.output_id()) {
+            Some(output_response) => output_response,
+            // conflicting transactions may reference outputs that never existed
+            None if conflict_reason.is_some() => continue,
+            None => {
+                return Err(ApiError::NonRetriable(format!(
+                    "output {} not found",
+                    utxo_input.output_id()
+                )))
+            }
+        };
+        let output = Output::try_from(&output_response.output)
+            .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
 
         operations.push(build_utxo_input_operation(
             utxo_input.output_id(),
@@ -209,44 +657,99 @@ async fn from_transaction(
         operations.push(output_operation);
     }
 
+    // the ledger was not changed by a conflicting transaction, so no coins were created or spent
+    if conflict_reason.is_some() {
+        for operation in operations.iter_mut() {
+            operation.status = Some(operation_status_skipped());
+            operation.coin_change = None;
+        }
+    }
+
     let transaction = BlockTransaction {
         transaction_identifier: TransactionIdentifier {
             hash: transaction_payload.id().to_string(),
         },
         operations,
+        metadata: Some(transaction_metadata(message_id, message, conflict_reason)),
     };
 
     Ok(transaction)
 }
 
-async fn from_milestone(
+/// Milestones only create outputs through receipts of the legacy-network migration. The migrated funds are minted and
+/// the treasury, which held them before, is reduced accordingly.
+fn from_milestone(
+    message_id: &MessageId,
+    message: &Message,
+    milestone: &MilestonePayload,
     created_outputs: &[OutputId],
-    iota_client: &Client,
+    output_cache: &OutputCache,
     rosetta_config: &RosettaConfig,
 ) -> Result<BlockTransaction, ApiError> {
+    let receipt = match milestone.essence().receipt() {
+        Some(Payload::Receipt(receipt)) => Some(receipt),
+        _ => None,
+    };
+
     let mut operations = Vec::new();
 
     for output_id in created_outputs {
-        let output = Output::try_from(
-            &iota_client
-                .get_output(&UtxoInput::from(*output_id))
-                .await
-                .map_err(|e| ApiError::NonRetriable(format!("can not get output: {}", e)))?
-                .output,
-        )
-        .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
+        let output_response = output_cache
+            .get(output_id)
+            .ok_or_else(|| ApiError::NonRetriable(format!("output {} not found", output_id)))?;
+        let output = Output::try_from(&output_response.output)
+            .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
 
-        let mint_operation =
+        let mut mint_operation =
             build_utxo_output_operation(Some(*output_id), &output, operations.len(), true, rosetta_config)?;
 
+        // the outputs of a receipt are indexed in the order of its migrated funds entries
+        if let Some(funds_entry) = receipt.and_then(|receipt| receipt.funds().get(output_id.index() as usize)) {
+            mint_operation.metadata = Some(migrated_funds_metadata(funds_entry.tail_transaction_hash()));
+        }
+
         operations.push(mint_operation);
     }
 
+    if let Some(receipt) = receipt {
+        let treasury_output = match receipt.transaction() {
+            Payload::TreasuryTransaction(treasury_transaction) => match treasury_transaction.output() {
+                Output::Treasury(treasury_output) => treasury_output,
+                _ => {
+                    return Err(ApiError::NonRetriable(
+                        "invalid output of treasury transaction".to_string(),
+                    ))
+                }
+            },
+            _ => return Err(ApiError::NonRetriable("invalid transaction of receipt".to_string())),
+        };
+
+        // the consumed treasury held the migrated funds as well
+        let migrated_amount = receipt
+            .funds()
+            .iter()
+            .map(|funds_entry| funds_entry.output().amount())
+            .sum::<u64>();
+        let consumed_amount = treasury_output.amount() + migrated_amount;
+
+        operations.push(build_treasury_operation(
+            TREASURY_INPUT,
+            -(consumed_amount as i64),
+            operations.len(),
+        ));
+        operations.push(build_treasury_operation(
+            TREASURY_OUTPUT,
+            treasury_output.amount() as i64,
+            operations.len(),
+        ));
+    }
+
     let transaction = BlockTransaction {
         transaction_identifier: TransactionIdentifier {
             hash: created_outputs.first().unwrap().transaction_id().to_string(),
         },
         operations,
+        metadata: Some(transaction_metadata(message_id, message, None)),
     };
 
     Ok(transaction)
@@ -0,0 +1,201 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::{get_message, get_milestone},
+    error::ApiError,
+    state::ServerState,
+    types::{BlockIdentifier, PartialBlockIdentifier},
+};
+
+use bee_message::prelude::*;
+use iota_client::{Client, MilestoneResponse};
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+/// Maps milestone indexes to the IDs of their milestone messages, which are used as block hashes, and back.
+#[derive(Clone, Default)]
+pub struct BlockHashIndex {
+    inner: Arc<RwLock<BlockHashes>>,
+}
+
+#[derive(Default)]
+struct BlockHashes {
+    hashes: HashMap<u32, String>,
+    indexes: HashMap<String, u32>,
+}
+
+impl BlockHashIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, milestone_index: u32, hash: String) {
+        let mut block_hashes = self.inner.write().unwrap();
+        block_hashes.indexes.insert(hash.clone(), milestone_index);
+        block_hashes.hashes.insert(milestone_index, hash);
+    }
+
+    pub fn hash(&self, milestone_index: u32) -> Option<String> {
+        self.inner.read().unwrap().hashes.get(&milestone_index).cloned()
+    }
+
+    pub fn index(&self, hash: &str) -> Option<u32> {
+        self.inner.read().unwrap().indexes.get(hash).copied()
+    }
+}
+
+/// Fetches the milestone and remembers its ID as block hash, and its timestamp.
+pub(crate) async fn milestone_with_hash(
+    milestone_index: u32,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<MilestoneResponse, ApiError> {
+    let milestone = get_milestone(milestone_index, iota_client).await?;
+    server_state
+        .block_hashes
+        .insert(milestone.index, milestone.message_id.to_string());
+    server_state
+        .milestone_timestamps
+        .insert(milestone.index, milestone.timestamp);
+    Ok(milestone)
+}
+
+pub(crate) async fn block_identifier(
+    milestone_index: u32,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<BlockIdentifier, ApiError> {
+    let hash = match server_state.block_hashes.hash(milestone_index) {
+        Some(hash) => hash,
+        None => milestone_with_hash(milestone_index, server_state, iota_client)
+            .await?
+            .message_id
+            .to_string(),
+    };
+
+    Ok(BlockIdentifier {
+        index: milestone_index,
+        hash,
+    })
+}
+
+/// Like `block_identifier`, but milestones the node already pruned are only identified if their ID is known locally
+/// or from the block cache, since it can not be looked up anymore.
+pub(crate) async fn block_identifier_unless_pruned(
+    milestone_index: u32,
+    pruning_index: u32,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<BlockIdentifier, ApiError> {
+    if milestone_index > pruning_index {
+        return block_identifier(milestone_index, server_state, iota_client).await;
+    }
+
+    match known_hash(milestone_index, server_state)? {
+        Some(hash) => Ok(BlockIdentifier {
+            index: milestone_index,
+            hash,
+        }),
+        None => Err(ApiError::MilestonePruned(format!(
+            "milestone {} was pruned by the node and its ID is not known",
+            milestone_index
+        ))),
+    }
+}
+
+/// Identifies the genesis block without failing: a genesis milestone the node already pruned, and whose ID is neither
+/// known locally nor from the block cache, is identified by the null message ID, which no milestone message has.
+pub(crate) async fn genesis_block_identifier(
+    pruning_index: u32,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<BlockIdentifier, ApiError> {
+    let genesis_index = server_state.genesis_index;
+    if genesis_index > pruning_index {
+        return block_identifier(genesis_index, server_state, iota_client).await;
+    }
+
+    let hash = known_hash(genesis_index, server_state)?.unwrap_or_else(|| MessageId::null().to_string());
+
+    Ok(BlockIdentifier {
+        index: genesis_index,
+        hash,
+    })
+}
+
+/// Returns the ID of the milestone if it is known without asking the node.
+pub(crate) fn known_hash(milestone_index: u32, server_state: &ServerState) -> Result<Option<String>, ApiError> {
+    if let Some(hash) = server_state.block_hashes.hash(milestone_index) {
+        return Ok(Some(hash));
+    }
+
+    if let Some(block_cache) = &server_state.block_cache {
+        if let Some(block_response) = block_cache.get(milestone_index)? {
+            let hash = block_response.block.block_identifier.hash;
+            server_state.block_hashes.insert(milestone_index, hash.clone());
+            return Ok(Some(hash));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Completes a partial block identifier; blocks that are only given by hash are resolved through the local index, or
+/// through their milestone message if the hash is not known yet.
+pub(crate) async fn resolve_block_identifier(
+    partial_block_identifier: &PartialBlockIdentifier,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<BlockIdentifier, ApiError> {
+    match (partial_block_identifier.index, &partial_block_identifier.hash) {
+        (Some(index), Some(hash)) => {
+            let block_identifier = block_identifier(index, server_state, iota_client).await?;
+            if &block_identifier.hash != hash {
+                return Err(ApiError::NonRetriable(
+                    "block index does not relate to block hash".to_string(),
+                ));
+            }
+            Ok(block_identifier)
+        }
+        (Some(index), None) => block_identifier(index, server_state, iota_client).await,
+        (None, Some(hash)) => {
+            let index = match server_state.block_hashes.index(hash) {
+                Some(index) => index,
+                None => milestone_index_of_message(hash, server_state, iota_client).await?,
+            };
+            Ok(BlockIdentifier {
+                index,
+                hash: hash.clone(),
+            })
+        }
+        (None, None) => Err(ApiError::NonRetriable(
+            "either block index or block hash must be set".to_string(),
+        )),
+    }
+}
+
+async fn milestone_index_of_message(
+    hash: &str,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<u32, ApiError> {
+    let message_id = hash
+        .parse::<MessageId>()
+        .map_err(|e| ApiError::NonRetriable(format!("invalid block hash: {}", e)))?;
+
+    match get_message(&message_id, iota_client).await?.payload() {
+        Some(Payload::Milestone(milestone)) => {
+            let index = *milestone.essence().index();
+            server_state.block_hashes.insert(index, hash.to_string());
+            Ok(index)
+        }
+        _ => Err(ApiError::NonRetriable(format!(
+            "invalid block hash: {} is not a milestone message",
+            hash
+        ))),
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::RosettaConfig,
+    data::block::{
+        block_handler::{
+            build_block_transaction, build_conflicting_transaction, check_not_before_genesis, check_not_pruned,
+            conflicting_transactions, created_outputs_of_milestone, messages_of_created_outputs, OutputCache,
+        },
+        block_hashes::resolve_block_identifier,
+    },
+    error::ApiError,
+    is_offline_mode_enabled, is_wrong_network,
+    state::ServerState,
+    types::{BlockIdentifier, BlockTransaction, NetworkIdentifier, PartialBlockIdentifier, TransactionIdentifier},
+};
+
+use bee_message::prelude::*;
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockTransactionRequest {
+    pub network_identifier: NetworkIdentifier,
+    pub block_identifier: BlockIdentifier,
+    pub transaction_identifier: TransactionIdentifier,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockTransactionResponse {
+    pub transaction: BlockTransaction,
+}
+
+pub async fn block_transaction(
+    request: BlockTransactionRequest,
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<BlockTransactionResponse, ApiError> {
+    debug!("/block/transaction");
+
+    if is_wrong_network(&rosetta_config, &request.network_identifier) {
+        return Err(ApiError::WrongNetwork);
+    }
+
+    if is_offline_mode_enabled(&rosetta_config) {
+        return Err(ApiError::UnavailableOffline);
+    }
+
+    let transaction_id = request
+        .transaction_identifier
+        .hash
+        .parse::<TransactionId>()
+        .map_err(|e| ApiError::NonRetriable(format!("invalid transaction hash: {}", e)))?;
+
+    // a cached block still contains its transactions after the node pruned the milestone
+    if let Some(block_cache) = &server_state.block_cache {
+        if let Some(block_response) = block_cache.get(request.block_identifier.index)? {
+            let block = block_response.block;
+            if block.block_identifier.hash == request.block_identifier.hash {
+                if let Some(transaction) = block
+                    .transactions
+                    .into_iter()
+                    .find(|transaction| transaction.transaction_identifier.hash == request.transaction_identifier.hash)
+                {
+                    return Ok(BlockTransactionResponse { transaction });
+                }
+            }
+        }
+    }
+
+    let client = server_state.client_pool.get().await?;
+
+    // blocks before the genesis and blocks the node pruned already are rejected before the node is asked for them
+    check_not_before_genesis(request.block_identifier.index, &server_state)?;
+    check_not_pruned(request.block_identifier.index, &client).await?;
+
+    let block_identifier = resolve_block_identifier(
+        &PartialBlockIdentifier {
+            index: Some(request.block_identifier.index),
+            hash: Some(request.block_identifier.hash),
+        },
+        &server_state,
+        &client,
+    )
+    .await?;
+    let milestone_index = block_identifier.index;
+
+    // only the outputs of the requested transaction are needed to find the message that contains it
+    let created_outputs = created_outputs_of_milestone(milestone_index, &server_state)
+        .await?
+        .into_iter()
+        .filter(|output_id| *output_id.transaction_id() == transaction_id)
+        .collect();
+
+    let mut output_cache = OutputCache::new();
+    let message_info = messages_of_created_outputs(created_outputs, &mut output_cache, &client)
+        .await?
+        .into_iter()
+        .next();
+
+    if let Some(message_info) = message_info {
+        return Ok(BlockTransactionResponse {
+            transaction: build_block_transaction(&message_info, &mut output_cache, &client, &rosetta_config).await?,
+        });
+    }
+
+    // conflicting transactions did not create any outputs
+    let conflicting_transaction = conflicting_transactions(&block_identifier, &client)
+        .await?
+        .into_iter()
+        .find(|conflicting_transaction| conflicting_transaction.payload.id() == transaction_id)
+        .ok_or_else(|| {
+            ApiError::NonRetriable(format!(
+                "transaction {} not found in block {}",
+                transaction_id, milestone_index
+            ))
+        })?;
+
+    Ok(BlockTransactionResponse {
+        transaction: build_conflicting_transaction(
+            &conflicting_transaction,
+            &mut output_cache,
+            &client,
+            &rosetta_config,
+        )
+        .await?,
+    })
+}
@@ -0,0 +1,118 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::get_node_info,
+    data::block::block_hashes::{block_identifier, milestone_with_hash},
+    error::ApiError,
+    state::ServerState,
+    types::BlockIdentifier,
+};
+
+use iota_client::Client;
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// Remembers the timestamps of fetched milestones, so repeated searches by timestamp hardly need to query the node.
+#[derive(Clone, Default)]
+pub struct MilestoneTimestamps {
+    timestamps: Arc<RwLock<BTreeMap<u32, u64>>>,
+}
+
+impl MilestoneTimestamps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, milestone_index: u32, timestamp: u64) {
+        self.timestamps.write().unwrap().insert(milestone_index, timestamp);
+    }
+
+    pub fn get(&self, milestone_index: u32) -> Option<u64> {
+        self.timestamps.read().unwrap().get(&milestone_index).copied()
+    }
+
+    /// Narrows the search range `low..=high` with the cached milestones: `low` moves to the latest cached milestone at
+    /// or before `timestamp`, `high` to the earliest cached milestone after it.
+    pub fn narrow(&self, timestamp: u64, low: u32, high: u32) -> (u32, u32) {
+        let timestamps = self.timestamps.read().unwrap();
+        let mut narrowed = (low, high);
+        for (milestone_index, milestone_timestamp) in timestamps.range(low..=high) {
+            if *milestone_timestamp <= timestamp {
+                narrowed.0 = *milestone_index;
+            } else {
+                narrowed.1 = *milestone_index;
+                break;
+            }
+        }
+        narrowed
+    }
+}
+
+/// Returns the latest milestone that was confirmed at or before `timestamp` (Unix time in seconds), together with its
+/// timestamp. Milestone timestamps never decrease, so the milestones still available on the node are binary searched.
+pub(crate) async fn find_milestone_by_timestamp(
+    timestamp: u64,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<(BlockIdentifier, u64), ApiError> {
+    let node_info = get_node_info(iota_client).await?;
+    let oldest_index = (node_info.pruning_index + 1).max(server_state.genesis_index);
+    let confirmed_index = node_info.confirmed_milestone_index;
+
+    let oldest_timestamp = milestone_timestamp(oldest_index, server_state, iota_client).await?;
+    if timestamp < oldest_timestamp {
+        if oldest_index == server_state.genesis_index {
+            return Err(ApiError::BlockBeforeGenesis(format!(
+                "timestamp {} is before the genesis milestone {}",
+                timestamp, oldest_index
+            )));
+        }
+        return Err(ApiError::MilestonePruned(format!(
+            "the milestones before timestamp {} were pruned by the node",
+            timestamp
+        )));
+    }
+
+    let confirmed_timestamp = milestone_timestamp(confirmed_index, server_state, iota_client).await?;
+    if confirmed_timestamp <= timestamp {
+        return Ok((
+            block_identifier(confirmed_index, server_state, iota_client).await?,
+            confirmed_timestamp,
+        ));
+    }
+
+    // the milestone at `low` is at or before the timestamp, the milestone at `high` after it
+    let (mut low, mut high) = server_state
+        .milestone_timestamps
+        .narrow(timestamp, oldest_index, confirmed_index);
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if milestone_timestamp(middle, server_state, iota_client).await? <= timestamp {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    Ok((
+        block_identifier(low, server_state, iota_client).await?,
+        milestone_timestamp(low, server_state, iota_client).await?,
+    ))
+}
+
+async fn milestone_timestamp(
+    milestone_index: u32,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<u64, ApiError> {
+    match server_state.milestone_timestamps.get(milestone_index) {
+        Some(timestamp) => Ok(timestamp),
+        None => Ok(milestone_with_hash(milestone_index, server_state, iota_client)
+            .await?
+            .timestamp),
+    }
+}
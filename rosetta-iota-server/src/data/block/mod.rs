@@ -3,19 +3,34 @@
 
 use crate::{
     config::RosettaConfig,
-    data::block::block_handler::block,
-    filters::{handle, with_rosetta_config},
+    data::block::{block_handler::block, block_transaction::block_transaction},
+    filters::{handle, with_rosetta_config, with_server_state},
+    state::ServerState,
 };
 
 use warp::Filter;
 
+pub mod block_cache;
 pub mod block_handler;
+pub mod block_hashes;
+pub mod block_transaction;
+pub mod milestone_timestamps;
 
-pub fn routes(options: RosettaConfig) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    warp::post().and(
-        warp::path!("block")
+pub fn routes(
+    options: RosettaConfig,
+    server_state: ServerState,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::post()
+        .and(
+            warp::path!("block")
+                .and(warp::body::json())
+                .and(with_rosetta_config(options.clone()))
+                .and(with_server_state(server_state.clone()))
+                .and_then(handle(block)),
+        )
+        .or(warp::path!("block" / "transaction")
             .and(warp::body::json())
             .and(with_rosetta_config(options))
-            .and_then(handle(block)),
-    )
+            .and(with_server_state(server_state))
+            .and_then(handle(block_transaction)))
 }
@@ -0,0 +1,309 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::{get_balance_of_address, get_message, get_message_metadata, get_node_info, get_output},
+    config::RosettaConfig,
+    data::block::{block_hashes::milestone_with_hash, milestone_timestamps::find_milestone_by_timestamp},
+    error::ApiError,
+    is_offline_mode_enabled, is_wrong_network,
+    state::ServerState,
+    types::{BlockIdentifier, CoinAction, IndexedBlocks, NetworkIdentifier, TransactionIdentifier},
+};
+
+use bee_message::prelude::*;
+use bee_rest_api::types::{
+    dtos::MessageDto,
+    responses::{MessageMetadataResponse, OutputResponse},
+};
+use iota_client::Client;
+
+use log::debug;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub const GET_MESSAGE: &str = "get_message";
+pub const GET_OUTPUT: &str = "get_output";
+pub const GET_MILESTONE: &str = "get_milestone";
+pub const NODE_INFO: &str = "node_info";
+pub const DUST_ALLOWED: &str = "dust_allowed";
+pub const FIND_BLOCK_BY_TIMESTAMP: &str = "find_block_by_timestamp";
+
+pub fn call_method_list() -> Vec<String> {
+    vec![
+        GET_MESSAGE.into(),
+        GET_OUTPUT.into(),
+        GET_MILESTONE.into(),
+        NODE_INFO.into(),
+        DUST_ALLOWED.into(),
+        FIND_BLOCK_BY_TIMESTAMP.into(),
+    ]
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CallRequest {
+    pub network_identifier: NetworkIdentifier,
+    pub method: String,
+    #[serde(default)]
+    pub parameters: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CallResponse {
+    pub result: Value,
+    pub idempotent: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetMessageParameters {
+    pub message_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetMessageResult {
+    pub message: MessageDto,
+    pub metadata: MessageMetadataResponse,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetOutputParameters {
+    pub output_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetOutputResult {
+    pub output: OutputResponse,
+    /// The milestone and transaction that spent the output. The node does not tell which milestone spent an output,
+    /// so they are only known if the output was spent in one of the `indexed_blocks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spent_in_block: Option<BlockIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spent_by_transaction: Option<TransactionIdentifier>,
+    /// The blocks that were searched for the spending transaction of a spent output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_blocks: Option<IndexedBlocks>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetMilestoneParameters {
+    pub index: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetMilestoneResult {
+    pub index: u32,
+    pub message_id: String,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodeInfoParameters {}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DustAllowedParameters {
+    pub address: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DustAllowedResult {
+    pub address: String,
+    pub dust_allowed: bool,
+    pub ledger_index: u32,
+}
+
+/// `timestamp` is a Unix timestamp in milliseconds, like the timestamps of blocks.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FindBlockByTimestampParameters {
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FindBlockByTimestampResult {
+    pub block_identifier: BlockIdentifier,
+    pub timestamp: u64,
+}
+
+pub async fn call(
+    request: CallRequest,
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<CallResponse, ApiError> {
+    debug!("/call");
+
+    if is_wrong_network(&rosetta_config, &request.network_identifier) {
+        return Err(ApiError::WrongNetwork);
+    }
+
+    if is_offline_mode_enabled(&rosetta_config) {
+        return Err(ApiError::UnavailableOffline);
+    }
+
+    let client = server_state.client_pool.get().await?;
+
+    let parameters = Value::Object(request.parameters);
+
+    let (result, idempotent) = match request.method.as_str() {
+        GET_MESSAGE => (
+            to_result(call_get_message(parse_parameters(parameters)?, &client).await?)?,
+            false,
+        ),
+        GET_OUTPUT => (
+            to_result(call_get_output(parse_parameters(parameters)?, &server_state, &client).await?)?,
+            false,
+        ),
+        // confirmed milestones never change
+        GET_MILESTONE => (
+            to_result(call_get_milestone(parse_parameters(parameters)?, &server_state, &client).await?)?,
+            true,
+        ),
+        NODE_INFO => {
+            let _: NodeInfoParameters = parse_parameters(parameters)?;
+            (to_result(get_node_info(&client).await?)?, false)
+        }
+        DUST_ALLOWED => (
+            to_result(call_dust_allowed(parse_parameters(parameters)?, &client).await?)?,
+            false,
+        ),
+        FIND_BLOCK_BY_TIMESTAMP => (
+            to_result(call_find_block_by_timestamp(parse_parameters(parameters)?, &server_state, &client).await?)?,
+            false,
+        ),
+        method => return Err(ApiError::NonRetriable(format!("unsupported call method: {}", method))),
+    };
+
+    Ok(CallResponse { result, idempotent })
+}
+
+fn parse_parameters<T: DeserializeOwned>(parameters: Value) -> Result<T, ApiError> {
+    serde_json::from_value(parameters).map_err(|e| ApiError::NonRetriable(format!("invalid parameters: {}", e)))
+}
+
+fn to_result<T: Serialize>(result: T) -> Result<Value, ApiError> {
+    serde_json::to_value(result).map_err(|e| ApiError::NonRetriable(format!("can not serialize result: {}", e)))
+}
+
+async fn call_get_message(
+    parameters: GetMessageParameters,
+    iota_client: &Client,
+) -> Result<GetMessageResult, ApiError> {
+    let message_id = parameters
+        .message_id
+        .parse::<MessageId>()
+        .map_err(|e| ApiError::NonRetriable(format!("invalid message id: {}", e)))?;
+
+    let message = get_message(&message_id, iota_client).await?;
+    let metadata = get_message_metadata(&message_id, iota_client).await?;
+
+    Ok(GetMessageResult {
+        message: MessageDto::from(&message),
+        metadata,
+    })
+}
+
+async fn call_get_output(
+    parameters: GetOutputParameters,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<GetOutputResult, ApiError> {
+    let output_id = parameters
+        .output_id
+        .parse::<OutputId>()
+        .map_err(|e| ApiError::NonRetriable(format!("invalid output id: {}", e)))?;
+
+    let output = get_output(output_id, iota_client).await?;
+
+    // the node does not tell which milestone spent an output, but the followed milestones do
+    let indexed_blocks = if output.is_spent {
+        server_state
+            .transaction_index
+            .indexed_range()
+            .map(IndexedBlocks::from_range)
+    } else {
+        None
+    };
+    let spending_transaction = if indexed_blocks.is_some() {
+        let identifier = output_id.to_string();
+        server_state
+            .transaction_index
+            .search(None, 0, 1, |transaction| {
+                transaction.operations.iter().any(|operation| {
+                    operation.coin_change.as_ref().map_or(false, |coin_change| {
+                        matches!(coin_change.coin_action, CoinAction::CoinSpent)
+                            && coin_change.coin_identifier.identifier == identifier
+                    })
+                })
+            })?
+            .1
+            .into_iter()
+            .next()
+    } else {
+        None
+    };
+
+    let (spent_in_block, spent_by_transaction) = match spending_transaction {
+        Some(indexed) => (
+            Some(indexed.block_identifier),
+            Some(indexed.transaction.transaction_identifier),
+        ),
+        None => (None, None),
+    };
+
+    Ok(GetOutputResult {
+        output,
+        spent_in_block,
+        spent_by_transaction,
+        indexed_blocks,
+    })
+}
+
+async fn call_get_milestone(
+    parameters: GetMilestoneParameters,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<GetMilestoneResult, ApiError> {
+    let milestone = milestone_with_hash(parameters.index, server_state, iota_client).await?;
+
+    Ok(GetMilestoneResult {
+        index: milestone.index,
+        message_id: milestone.message_id.to_string(),
+        timestamp: milestone.timestamp,
+    })
+}
+
+async fn call_dust_allowed(
+    parameters: DustAllowedParameters,
+    iota_client: &Client,
+) -> Result<DustAllowedResult, ApiError> {
+    Address::try_from_bech32(&parameters.address)
+        .map_err(|e| ApiError::InvalidAddress(format!("can not parse {}: {}", parameters.address, e)))?;
+
+    let balance = get_balance_of_address(&parameters.address, iota_client).await?;
+
+    Ok(DustAllowedResult {
+        address: parameters.address,
+        dust_allowed: balance.dust_allowed,
+        ledger_index: balance.ledger_index,
+    })
+}
+
+async fn call_find_block_by_timestamp(
+    parameters: FindBlockByTimestampParameters,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<FindBlockByTimestampResult, ApiError> {
+    let (block_identifier, timestamp) =
+        find_milestone_by_timestamp(parameters.timestamp / 1000, server_state, iota_client).await?;
+
+    Ok(FindBlockByTimestampResult {
+        block_identifier,
+        timestamp: timestamp * 1000,
+    })
+}
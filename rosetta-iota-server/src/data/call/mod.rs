@@ -0,0 +1,26 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::RosettaConfig,
+    data::call::call_handler::call,
+    filters::{handle, with_rosetta_config, with_server_state},
+    state::ServerState,
+};
+
+use warp::Filter;
+
+pub mod call_handler;
+
+pub fn routes(
+    options: RosettaConfig,
+    server_state: ServerState,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::post().and(
+        warp::path!("call")
+            .and(warp::body::json())
+            .and(with_rosetta_config(options))
+            .and(with_server_state(server_state))
+            .and_then(handle(call)),
+    )
+}
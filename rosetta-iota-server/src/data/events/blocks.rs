@@ -0,0 +1,57 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::RosettaConfig,
+    consts::MAX_EVENTS_LIMIT,
+    error::ApiError,
+    is_offline_mode_enabled, is_wrong_network,
+    state::ServerState,
+    types::{BlockEvent, NetworkIdentifier},
+};
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventsBlocksRequest {
+    pub network_identifier: NetworkIdentifier,
+    pub offset: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventsBlocksResponse {
+    pub max_sequence: u64,
+    pub events: Vec<BlockEvent>,
+}
+
+pub async fn events_blocks(
+    request: EventsBlocksRequest,
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<EventsBlocksResponse, ApiError> {
+    debug!("/events/blocks");
+
+    if is_wrong_network(&rosetta_config, &request.network_identifier) {
+        return Err(ApiError::WrongNetwork);
+    }
+
+    if is_offline_mode_enabled(&rosetta_config) {
+        return Err(ApiError::UnavailableOffline);
+    }
+
+    let limit = match request.limit {
+        Some(limit) => (limit as usize).min(MAX_EVENTS_LIMIT),
+        None => MAX_EVENTS_LIMIT,
+    };
+
+    let event_log = &server_state.event_log;
+
+    Ok(EventsBlocksResponse {
+        max_sequence: event_log.max_sequence().unwrap_or(0),
+        events: event_log.events(request.offset.unwrap_or(0), limit),
+    })
+}
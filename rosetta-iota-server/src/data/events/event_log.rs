@@ -0,0 +1,63 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::types::{BlockEvent, BlockEventType, BlockIdentifier};
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// In-memory log of the blocks the server has seen being confirmed. The sequence number of an event is derived from
+/// the index of its block, counted from the genesis block, so it stays the same when the log is rebuilt after a
+/// restart.
+#[derive(Clone)]
+pub struct EventLog {
+    genesis_index: u32,
+    events: Arc<RwLock<BTreeMap<u64, BlockEvent>>>,
+}
+
+impl EventLog {
+    pub fn new(genesis_index: u32) -> Self {
+        Self {
+            genesis_index,
+            events: Default::default(),
+        }
+    }
+
+    pub fn block_added(&self, block_identifier: BlockIdentifier) {
+        let sequence = block_identifier.index.saturating_sub(self.genesis_index) as u64;
+        self.events.write().unwrap().insert(
+            sequence,
+            BlockEvent {
+                sequence,
+                block_identifier,
+                type_: BlockEventType::BlockAdded,
+            },
+        );
+    }
+
+    pub fn last_block_index(&self) -> Option<u32> {
+        self.events
+            .read()
+            .unwrap()
+            .values()
+            .next_back()
+            .map(|event| event.block_identifier.index)
+    }
+
+    pub fn max_sequence(&self) -> Option<u64> {
+        self.events.read().unwrap().keys().next_back().copied()
+    }
+
+    /// Returns up to `limit` events with a sequence number of at least `offset`.
+    pub fn events(&self, offset: u64, limit: usize) -> Vec<BlockEvent> {
+        self.events
+            .read()
+            .unwrap()
+            .range(offset..)
+            .take(limit)
+            .map(|(_, event)| event.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::RosettaConfig,
+    data::events::blocks::events_blocks,
+    filters::{handle, with_rosetta_config, with_server_state},
+    state::ServerState,
+};
+
+use warp::Filter;
+
+pub mod blocks;
+pub mod event_log;
+
+pub fn routes(
+    options: RosettaConfig,
+    server_state: ServerState,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::post().and(
+        warp::path!("events" / "blocks")
+            .and(warp::body::json())
+            .and(with_rosetta_config(options))
+            .and(with_server_state(server_state))
+            .and_then(handle(events_blocks)),
+    )
+}
@@ -0,0 +1,122 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::{get_message, get_message_metadata, get_tips},
+    config::RosettaConfig,
+    consts::{MAX_CONCURRENT_NODE_REQUESTS, MAX_MEMPOOL_MESSAGES},
+    error::ApiError,
+    is_offline_mode_enabled, is_wrong_network,
+    state::ServerState,
+    types::{NetworkIdentifier, TransactionIdentifier},
+};
+
+use bee_message::{payload::transaction::Essence, prelude::*};
+
+use futures::{stream, StreamExt, TryStreamExt};
+use iota_client::Client;
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MempoolRequest {
+    pub network_identifier: NetworkIdentifier,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MempoolResponse {
+    pub transaction_identifiers: Vec<TransactionIdentifier>,
+}
+
+pub async fn mempool(
+    request: MempoolRequest,
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<MempoolResponse, ApiError> {
+    debug!("/mempool");
+
+    if is_wrong_network(&rosetta_config, &request.network_identifier) {
+        return Err(ApiError::WrongNetwork);
+    }
+
+    if is_offline_mode_enabled(&rosetta_config) {
+        return Err(ApiError::UnavailableOffline);
+    }
+
+    let client = server_state.client_pool.get().await?;
+
+    let mut transaction_identifiers = server_state
+        .mempool_snapshot
+        .pending_transactions(&client)
+        .await?
+        .keys()
+        .map(|transaction_id| TransactionIdentifier {
+            hash: transaction_id.to_string(),
+        })
+        .collect::<Vec<_>>();
+    transaction_identifiers.sort_by(|a, b| a.hash.cmp(&b.hash));
+
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Collects the transaction payloads of solid messages that are not referenced by a milestone yet. The Tangle is
+/// walked from the tips towards their parents until referenced messages are reached; the messages of each step are
+/// fetched concurrently.
+pub(crate) async fn pending_transactions(
+    iota_client: &Client,
+) -> Result<HashMap<TransactionId, Box<TransactionPayload>>, ApiError> {
+    let mut pending_transactions = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut to_visit = get_tips(iota_client).await?;
+
+    while !to_visit.is_empty() {
+        let mut unvisited = Vec::new();
+        for message_id in to_visit {
+            if visited.len() == MAX_MEMPOOL_MESSAGES {
+                break;
+            }
+            if visited.insert(message_id) {
+                unvisited.push(message_id);
+            }
+        }
+
+        let unreferenced_messages = stream::iter(unvisited)
+            .map(|message_id| async move {
+                let metadata = get_message_metadata(&message_id, iota_client).await?;
+                if !metadata.is_solid || metadata.referenced_by_milestone_index.is_some() {
+                    return Ok(None);
+                }
+                get_message(&message_id, iota_client).await.map(Some)
+            })
+            .buffer_unordered(MAX_CONCURRENT_NODE_REQUESTS)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        to_visit = Vec::new();
+        for message in unreferenced_messages.into_iter().flatten() {
+            if let Some(Payload::Transaction(transaction)) = message.payload() {
+                pending_transactions.insert(transaction.id(), transaction.clone());
+            }
+            to_visit.extend(message.parents().iter().copied());
+        }
+    }
+
+    Ok(pending_transactions)
+}
+
+/// Returns the output if it was created by one of the pending transactions.
+pub(crate) fn pending_output(
+    output_id: &OutputId,
+    pending_transactions: &HashMap<TransactionId, Box<TransactionPayload>>,
+) -> Option<Output> {
+    let transaction = pending_transactions.get(output_id.transaction_id())?;
+    let Essence::Regular(regular_essence) = transaction.essence();
+    regular_essence.outputs().get(output_id.index() as usize).cloned()
+}
@@ -0,0 +1,43 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{consts::MEMPOOL_SNAPSHOT_TTL, data::mempool::mempool_handler::pending_transactions, error::ApiError};
+
+use bee_message::prelude::*;
+
+use iota_client::Client;
+use tokio::sync::Mutex;
+
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
+type PendingTransactions = HashMap<TransactionId, Box<TransactionPayload>>;
+
+/// Shares the pending transactions between requests for `MEMPOOL_SNAPSHOT_TTL`, so /mempool, /mempool/transaction and
+/// /account/coins do not walk the Tangle on every request.
+#[derive(Clone, Default)]
+pub struct MempoolSnapshot {
+    snapshot: Arc<Mutex<Option<(Instant, Arc<PendingTransactions>)>>>,
+}
+
+impl MempoolSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the pending transactions of the current snapshot; an outdated snapshot is replaced first. Concurrent
+    /// requests wait for the same walk instead of starting their own.
+    pub async fn pending_transactions(&self, iota_client: &Client) -> Result<Arc<PendingTransactions>, ApiError> {
+        let mut snapshot = self.snapshot.lock().await;
+
+        if let Some((taken_at, pending_transactions)) = snapshot.as_ref() {
+            if taken_at.elapsed() < MEMPOOL_SNAPSHOT_TTL {
+                return Ok(pending_transactions.clone());
+            }
+        }
+
+        let pending_transactions = Arc::new(pending_transactions(iota_client).await?);
+        *snapshot = Some((Instant::now(), pending_transactions.clone()));
+
+        Ok(pending_transactions)
+    }
+}
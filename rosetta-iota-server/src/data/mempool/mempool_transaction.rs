@@ -0,0 +1,124 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::get_output,
+    config::RosettaConfig,
+    data::mempool::mempool_handler::pending_output,
+    error::ApiError,
+    is_offline_mode_enabled, is_wrong_network,
+    operations::*,
+    state::ServerState,
+    types::{BlockTransaction, NetworkIdentifier, TransactionIdentifier},
+};
+
+use bee_message::{
+    payload::transaction::Essence,
+    prelude::{Output, *},
+};
+
+use iota_client::Client;
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use std::{collections::HashMap, convert::TryFrom};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MempoolTransactionRequest {
+    pub network_identifier: NetworkIdentifier,
+    pub transaction_identifier: TransactionIdentifier,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MempoolTransactionResponse {
+    pub transaction: BlockTransaction,
+}
+
+pub async fn mempool_transaction(
+    request: MempoolTransactionRequest,
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<MempoolTransactionResponse, ApiError> {
+    debug!("/mempool/transaction");
+
+    if is_wrong_network(&rosetta_config, &request.network_identifier) {
+        return Err(ApiError::WrongNetwork);
+    }
+
+    if is_offline_mode_enabled(&rosetta_config) {
+        return Err(ApiError::UnavailableOffline);
+    }
+
+    let transaction_id = request
+        .transaction_identifier
+        .hash
+        .parse::<TransactionId>()
+        .map_err(|e| ApiError::NonRetriable(format!("invalid transaction hash: {}", e)))?;
+
+    let client = server_state.client_pool.get().await?;
+
+    let pending_transactions = server_state.mempool_snapshot.pending_transactions(&client).await?;
+
+    let transaction_payload = pending_transactions
+        .get(&transaction_id)
+        .ok_or_else(|| ApiError::NonRetriable(format!("transaction {} not found in mempool", transaction_id)))?;
+
+    Ok(MempoolTransactionResponse {
+        transaction: from_pending_transaction(transaction_payload, &pending_transactions, &client, &rosetta_config)
+            .await?,
+    })
+}
+
+async fn from_pending_transaction(
+    transaction_payload: &TransactionPayload,
+    pending_transactions: &HashMap<TransactionId, Box<TransactionPayload>>,
+    iota_client: &Client,
+    rosetta_config: &RosettaConfig,
+) -> Result<BlockTransaction, ApiError> {
+    let Essence::Regular(regular_essence) = transaction_payload.essence();
+
+    let mut operations = Vec::new();
+
+    for input in regular_essence.inputs() {
+        let utxo_input = match input {
+            Input::Utxo(i) => i,
+            _ => return Err(ApiError::NonRetriable("unknown UTXO type".to_string())),
+        };
+
+        // inputs may spend outputs of other transactions that are still pending
+        let output = match pending_output(utxo_input.output_id(), pending_transactions) {
+            Some(output) => output,
+            None => Output::try_from(&get_output(*utxo_input.output_id(), iota_client).await?.output)
+                .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?,
+        };
+
+        operations.push(build_utxo_input_operation(
+            utxo_input.output_id(),
+            &output,
+            operations.len(),
+            false,
+            rosetta_config,
+        )?);
+    }
+
+    for (output_index, output) in regular_essence.outputs().iter().enumerate() {
+        let output_id = OutputId::new(transaction_payload.id(), output_index as u16)
+            .map_err(|e| ApiError::NonRetriable(format!("can not parse output id: {}", e)))?;
+
+        let output_operation =
+            build_utxo_output_operation(Some(output_id), output, operations.len(), false, rosetta_config)?;
+
+        operations.push(output_operation);
+    }
+
+    Ok(BlockTransaction {
+        transaction_identifier: TransactionIdentifier {
+            hash: transaction_payload.id().to_string(),
+        },
+        operations,
+        metadata: None,
+    })
+}
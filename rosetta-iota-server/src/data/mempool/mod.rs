@@ -0,0 +1,34 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::RosettaConfig,
+    data::mempool::{mempool_handler::mempool, mempool_transaction::mempool_transaction},
+    filters::{handle, with_rosetta_config, with_server_state},
+    state::ServerState,
+};
+
+use warp::Filter;
+
+pub mod mempool_handler;
+pub mod mempool_snapshot;
+pub mod mempool_transaction;
+
+pub fn routes(
+    options: RosettaConfig,
+    server_state: ServerState,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::post()
+        .and(
+            warp::path!("mempool")
+                .and(warp::body::json())
+                .and(with_rosetta_config(options.clone()))
+                .and(with_server_state(server_state.clone()))
+                .and_then(handle(mempool)),
+        )
+        .or(warp::path!("mempool" / "transaction")
+            .and(warp::body::json())
+            .and(with_rosetta_config(options))
+            .and(with_server_state(server_state))
+            .and_then(handle(mempool_transaction)))
+}
@@ -3,4 +3,8 @@
 
 pub mod account;
 pub mod block;
+pub mod call;
+pub mod events;
+pub mod mempool;
 pub mod network;
+pub mod search;
@@ -1,7 +1,9 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{config::RosettaConfig, consts, error::ApiError, filters::EmptyRequest, types::NetworkIdentifier};
+use crate::{
+    config::RosettaConfig, consts, error::ApiError, filters::EmptyRequest, state::ServerState, types::NetworkIdentifier,
+};
 
 use log::debug;
 use serde::{Deserialize, Serialize};
@@ -12,7 +14,11 @@ pub struct NetworkListResponse {
     pub network_identifiers: Vec<NetworkIdentifier>,
 }
 
-pub async fn network_list(_empty: EmptyRequest, options: RosettaConfig) -> Result<NetworkListResponse, ApiError> {
+pub async fn network_list(
+    _empty: EmptyRequest,
+    options: RosettaConfig,
+    _server_state: ServerState,
+) -> Result<NetworkListResponse, ApiError> {
     debug!("/network/list");
 
     let response = NetworkListResponse {
@@ -4,9 +4,11 @@
 use crate::{
     config::RosettaConfig,
     consts,
+    data::call::call_handler::call_method_list,
     error::ApiError,
     is_wrong_network,
     operations::*,
+    state::ServerState,
     types::{NetworkIdentifier, *},
 };
 
@@ -29,16 +31,23 @@ pub struct NetworkOptionsResponse {
 pub async fn network_options(
     request: NetworkOptionsRequest,
     rosetta_config: RosettaConfig,
+    server_state: ServerState,
 ) -> Result<NetworkOptionsResponse, ApiError> {
     debug!("/network/options");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     let version = Version {
         rosetta_version: consts::ROSETTA_VERSION.to_string(),
         node_version: consts::NODE_VERSION.to_string(),
+        metadata: Some(VersionMetadata {
+            indexed_blocks: server_state
+                .transaction_index
+                .indexed_range()
+                .map(IndexedBlocks::from_range),
+        }),
     };
 
     let operation_statuses = vec![
@@ -60,10 +69,10 @@ pub async fn network_options(
         operation_statuses,
         operation_types,
         errors,
-        historical_balance_lookup: false,
-        call_methods: vec![],
+        historical_balance_lookup: server_state.balance_store.is_some(),
+        call_methods: call_method_list(),
         balance_exemptions: vec![],
-        mempool_coins: false,
+        mempool_coins: true,
     };
 
     let response = NetworkOptionsResponse { version, allow };
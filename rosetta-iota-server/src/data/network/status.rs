@@ -2,13 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client::{build_client, get_confirmed_milestone, get_latest_milestone_index, get_peers, get_pruning_index},
+    client::{get_confirmed_milestone, get_latest_milestone_index, get_peers, get_pruning_index},
     config::RosettaConfig,
+    data::block::{
+        block_handler::oldest_block_index,
+        block_hashes::{block_identifier, genesis_block_identifier},
+    },
     error::ApiError,
     is_offline_mode_enabled, is_wrong_network,
+    state::ServerState,
     types::{NetworkIdentifier, *},
 };
 
+use iota_client::Client;
 use log::debug;
 use serde::{Deserialize, Serialize};
 
@@ -32,23 +38,25 @@ pub struct NetworkStatusResponse {
 pub async fn network_status(
     request: NetworkStatusRequest,
     rosetta_config: RosettaConfig,
+    server_state: ServerState,
 ) -> Result<NetworkStatusResponse, ApiError> {
     debug!("/network/status");
 
     if is_wrong_network(&rosetta_config, &request.network_identifier) {
-        return Err(ApiError::NonRetriable("wrong network".to_string()));
+        return Err(ApiError::WrongNetwork);
     }
 
     if is_offline_mode_enabled(&rosetta_config) {
-        return Err(ApiError::NonRetriable(
-            "endpoint does not support offline mode".to_string(),
-        ));
+        return Err(ApiError::UnavailableOffline);
     }
 
-    let client = build_client(&rosetta_config).await?;
+    let client = server_state.client_pool.get().await?;
 
     let confirmed_milestone = get_confirmed_milestone(&client).await?;
-    let oldest_block = get_pruning_index(&client).await? + 1;
+    server_state
+        .block_hashes
+        .insert(confirmed_milestone.index, confirmed_milestone.message_id.to_string());
+    let pruning_index = get_pruning_index(&client).await?;
     let latest_milestone_index = get_latest_milestone_index(&client).await?;
 
     let mut peers = vec![];
@@ -59,17 +67,12 @@ pub async fn network_status(
     let response = NetworkStatusResponse {
         current_block_identifier: BlockIdentifier {
             index: confirmed_milestone.index,
-            hash: confirmed_milestone.index.to_string(),
+            hash: confirmed_milestone.message_id.to_string(),
         },
         current_block_timestamp: confirmed_milestone.timestamp * 1000,
-        genesis_block_identifier: BlockIdentifier {
-            index: 1,
-            hash: 1.to_string(),
-        },
-        oldest_block_identifier: BlockIdentifier {
-            index: oldest_block,
-            hash: oldest_block.to_string(),
-        },
+        genesis_block_identifier: genesis_block_identifier(pruning_index, &server_state, &client).await?,
+        oldest_block_identifier: oldest_block_identifier(pruning_index, &rosetta_config, &server_state, &client)
+            .await?,
         sync_status: SyncStatus {
             current_index: confirmed_milestone.index as u64,
             target_index: latest_milestone_index as u64,
@@ -80,3 +83,24 @@ pub async fn network_status(
 
     Ok(response)
 }
+
+/// The oldest block is the first one the node did not prune, unless the blocks before it are cached.
+async fn oldest_block_identifier(
+    pruning_index: u32,
+    rosetta_config: &RosettaConfig,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<BlockIdentifier, ApiError> {
+    let oldest_available_index = match &server_state.block_cache {
+        Some(block_cache) => block_cache.oldest_index(pruning_index + 1),
+        None => pruning_index + 1,
+    };
+    let oldest_index = oldest_block_index(oldest_available_index, pruning_index, rosetta_config, server_state)?;
+
+    if let Some(block_cache) = &server_state.block_cache {
+        if let Some(block_response) = block_cache.get(oldest_index)? {
+            return Ok(block_response.block.block_identifier);
+        }
+    }
+    block_identifier(oldest_index, server_state, iota_client).await
+}
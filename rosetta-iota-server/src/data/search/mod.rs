@@ -0,0 +1,27 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::RosettaConfig,
+    data::search::transactions::search_transactions,
+    filters::{handle, with_rosetta_config, with_server_state},
+    state::ServerState,
+};
+
+use warp::Filter;
+
+pub mod transaction_index;
+pub mod transactions;
+
+pub fn routes(
+    options: RosettaConfig,
+    server_state: ServerState,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::post().and(
+        warp::path!("search" / "transactions")
+            .and(warp::body::json())
+            .and(with_rosetta_config(options))
+            .and(with_server_state(server_state))
+            .and_then(handle(search_transactions)),
+    )
+}
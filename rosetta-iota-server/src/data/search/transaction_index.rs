@@ -0,0 +1,108 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consts::MAX_INDEXED_BLOCKS,
+    error::ApiError,
+    types::{BlockIdentifier, BlockTransaction, IndexedTransaction},
+};
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, RwLock},
+};
+
+/// In-memory index of the transactions of the followed milestones, ordered by block. Only the latest
+/// `MAX_INDEXED_BLOCKS` blocks are kept.
+#[derive(Clone, Default)]
+pub struct TransactionIndex {
+    blocks: Arc<RwLock<VecDeque<IndexedBlock>>>,
+}
+
+struct IndexedBlock {
+    block_identifier: BlockIdentifier,
+    transactions: Vec<BlockTransaction>,
+}
+
+impl TransactionIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_block(&self, block_identifier: &BlockIdentifier, transactions: Vec<BlockTransaction>) {
+        let mut blocks = self.blocks.write().unwrap();
+        blocks.push_back(IndexedBlock {
+            block_identifier: block_identifier.clone(),
+            transactions,
+        });
+        while blocks.len() > MAX_INDEXED_BLOCKS {
+            blocks.pop_front();
+        }
+    }
+
+    /// Returns the indexes of the oldest and the latest indexed block.
+    pub fn indexed_range(&self) -> Option<(u32, u32)> {
+        let blocks = self.blocks.read().unwrap();
+        Some((
+            blocks.front()?.block_identifier.index,
+            blocks.back()?.block_identifier.index,
+        ))
+    }
+
+    /// Returns the number of matching transactions of blocks up to `max_block` and the page of them that starts at
+    /// `offset`, newest first. Fails if `max_block` is outside the indexed blocks.
+    pub fn search<F>(
+        &self,
+        max_block: Option<u32>,
+        offset: usize,
+        limit: usize,
+        matches: F,
+    ) -> Result<(usize, Vec<IndexedTransaction>), ApiError>
+    where
+        F: Fn(&BlockTransaction) -> bool,
+    {
+        if let Some(max_block) = max_block {
+            match self.indexed_range() {
+                Some((oldest_index, _)) if max_block < oldest_index => {
+                    return Err(ApiError::NonRetriable(format!(
+                        "block {} is not indexed, the oldest indexed block is {}",
+                        max_block, oldest_index
+                    )));
+                }
+                Some((_, latest_index)) if max_block <= latest_index => {}
+                _ => {
+                    return Err(ApiError::Retriable(format!("block {} is not indexed yet", max_block)));
+                }
+            }
+        }
+
+        let blocks = self.blocks.read().unwrap();
+        let matching_transactions = blocks
+            .iter()
+            .rev()
+            .filter(|block| max_block.map_or(true, |max_block| block.block_identifier.index <= max_block))
+            .flat_map(|block| {
+                block
+                    .transactions
+                    .iter()
+                    .rev()
+                    .map(move |transaction| (&block.block_identifier, transaction))
+            })
+            .filter(|(_, transaction)| matches(transaction));
+
+        let mut total_count = 0;
+        let mut page = Vec::new();
+        for (block_identifier, transaction) in matching_transactions {
+            // only the transactions of the requested page are cloned
+            if total_count >= offset && page.len() < limit {
+                page.push(IndexedTransaction {
+                    block_identifier: block_identifier.clone(),
+                    transaction: transaction.clone(),
+                });
+            }
+            total_count += 1;
+        }
+
+        Ok((total_count, page))
+    }
+}
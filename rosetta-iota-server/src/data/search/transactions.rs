@@ -0,0 +1,154 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::RosettaConfig,
+    consts::MAX_SEARCH_LIMIT,
+    error::ApiError,
+    is_offline_mode_enabled, is_wrong_network,
+    operations::SUCCESS,
+    state::ServerState,
+    types::{
+        AccountIdentifier, BlockTransaction, CoinIdentifier, IndexedTransaction, NetworkIdentifier, Operator,
+        TransactionIdentifier,
+    },
+};
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchTransactionsRequest {
+    pub network_identifier: NetworkIdentifier,
+    pub operator: Option<Operator>,
+    pub max_block: Option<u32>,
+    pub offset: Option<u64>,
+    pub limit: Option<u64>,
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    pub account_identifier: Option<AccountIdentifier>,
+    pub coin_identifier: Option<CoinIdentifier>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub status: Option<String>,
+    pub success: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchTransactionsResponse {
+    pub transactions: Vec<IndexedTransaction>,
+    pub total_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
+type Condition = Box<dyn Fn(&BlockTransaction) -> bool + Send + Sync>;
+
+pub async fn search_transactions(
+    request: SearchTransactionsRequest,
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<SearchTransactionsResponse, ApiError> {
+    debug!("/search/transactions");
+
+    if is_wrong_network(&rosetta_config, &request.network_identifier) {
+        return Err(ApiError::WrongNetwork);
+    }
+
+    if is_offline_mode_enabled(&rosetta_config) {
+        return Err(ApiError::UnavailableOffline);
+    }
+
+    let offset = request.offset.unwrap_or(0) as usize;
+    let limit = match request.limit {
+        Some(limit) => (limit as usize).min(MAX_SEARCH_LIMIT),
+        None => MAX_SEARCH_LIMIT,
+    };
+    let operator = request.operator.clone().unwrap_or(Operator::And);
+    let max_block = request.max_block;
+    let conditions = conditions(request);
+
+    let (total_count, transactions) =
+        server_state
+            .transaction_index
+            .search(max_block, offset, limit, |transaction| {
+                if conditions.is_empty() {
+                    return true;
+                }
+                match operator {
+                    Operator::And => conditions.iter().all(|condition| condition(transaction)),
+                    Operator::Or => conditions.iter().any(|condition| condition(transaction)),
+                }
+            })?;
+
+    let next_offset = if offset + transactions.len() < total_count {
+        Some((offset + transactions.len()) as u64)
+    } else {
+        None
+    };
+
+    Ok(SearchTransactionsResponse {
+        transactions,
+        total_count: total_count as u64,
+        next_offset,
+    })
+}
+
+fn conditions(request: SearchTransactionsRequest) -> Vec<Condition> {
+    let mut conditions: Vec<Condition> = Vec::new();
+
+    if let Some(transaction_identifier) = request.transaction_identifier {
+        conditions.push(Box::new(move |transaction| {
+            transaction.transaction_identifier.hash == transaction_identifier.hash
+        }));
+    }
+
+    if let Some(account_identifier) = request.account_identifier {
+        conditions.push(Box::new(move |transaction| {
+            transaction.operations.iter().any(|operation| {
+                operation
+                    .account
+                    .as_ref()
+                    .map_or(false, |account| account.address == account_identifier.address)
+            })
+        }));
+    }
+
+    if let Some(coin_identifier) = request.coin_identifier {
+        conditions.push(Box::new(move |transaction| {
+            transaction.operations.iter().any(|operation| {
+                operation.coin_change.as_ref().map_or(false, |coin_change| {
+                    coin_change.coin_identifier.identifier == coin_identifier.identifier
+                })
+            })
+        }));
+    }
+
+    if let Some(type_) = request.type_ {
+        conditions.push(Box::new(move |transaction| {
+            transaction.operations.iter().any(|operation| operation.type_ == type_)
+        }));
+    }
+
+    if let Some(status) = request.status {
+        conditions.push(Box::new(move |transaction| {
+            transaction
+                .operations
+                .iter()
+                .any(|operation| operation.status.as_deref() == Some(status.as_str()))
+        }));
+    }
+
+    if let Some(success) = request.success {
+        conditions.push(Box::new(move |transaction| {
+            let successful = transaction
+                .operations
+                .iter()
+                .any(|operation| operation.status.as_deref() == Some(SUCCESS));
+            successful == success
+        }));
+    }
+
+    conditions
+}
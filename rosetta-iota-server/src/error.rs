@@ -6,12 +6,31 @@ use crate::types::{self, ErrorDetails};
 use thiserror::Error;
 use warp::{http::StatusCode, reply::Reply};
 
+/// Error codes are part of the public API and must never change; new variants get the next free code.
 #[derive(Debug, Error)]
 pub enum ApiError {
     #[error("non retriable error")]
     NonRetriable(String),
     #[error("retriable error")]
     Retriable(String),
+    #[error("request was made for wrong network")]
+    WrongNetwork,
+    #[error("endpoint is not available in offline mode")]
+    UnavailableOffline,
+    #[error("output type not supported")]
+    UnsupportedOutputType(String),
+    #[error("invalid address")]
+    InvalidAddress(String),
+    #[error("milestone pruned")]
+    MilestonePruned(String),
+    #[error("output already spent")]
+    OutputAlreadySpent(String),
+    #[error("node unreachable")]
+    NodeUnreachable(String),
+    #[error("block before genesis")]
+    BlockBeforeGenesis(String),
+    #[error("invalid dust output")]
+    InvalidDustOutput(String),
 }
 
 impl ApiError {
@@ -19,6 +38,15 @@ impl ApiError {
         match self {
             ApiError::NonRetriable(_) => 1,
             ApiError::Retriable(_) => 2,
+            ApiError::WrongNetwork => 3,
+            ApiError::UnavailableOffline => 4,
+            ApiError::UnsupportedOutputType(_) => 5,
+            ApiError::InvalidAddress(_) => 6,
+            ApiError::MilestonePruned(_) => 7,
+            ApiError::OutputAlreadySpent(_) => 8,
+            ApiError::NodeUnreachable(_) => 9,
+            ApiError::BlockBeforeGenesis(_) => 10,
+            ApiError::InvalidDustOutput(_) => 11,
         }
     }
 
@@ -26,6 +54,31 @@ impl ApiError {
         match self {
             ApiError::NonRetriable(_) => false,
             ApiError::Retriable(_) => true,
+            ApiError::WrongNetwork => false,
+            ApiError::UnavailableOffline => false,
+            ApiError::UnsupportedOutputType(_) => false,
+            ApiError::InvalidAddress(_) => false,
+            ApiError::MilestonePruned(_) => false,
+            ApiError::OutputAlreadySpent(_) => false,
+            ApiError::NodeUnreachable(_) => true,
+            ApiError::BlockBeforeGenesis(_) => false,
+            ApiError::InvalidDustOutput(_) => false,
+        }
+    }
+
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ApiError::NonRetriable(_) => "NonRetriable",
+            ApiError::Retriable(_) => "Retriable",
+            ApiError::WrongNetwork => "WrongNetwork",
+            ApiError::UnavailableOffline => "UnavailableOffline",
+            ApiError::UnsupportedOutputType(_) => "UnsupportedOutputType",
+            ApiError::InvalidAddress(_) => "InvalidAddress",
+            ApiError::MilestonePruned(_) => "MilestonePruned",
+            ApiError::OutputAlreadySpent(_) => "OutputAlreadySpent",
+            ApiError::NodeUnreachable(_) => "NodeUnreachable",
+            ApiError::BlockBeforeGenesis(_) => "BlockBeforeGenesis",
+            ApiError::InvalidDustOutput(_) => "InvalidDustOutput",
         }
     }
 
@@ -33,36 +86,52 @@ impl ApiError {
         format!("{}", self)
     }
 
-    pub(crate) fn details(&self) -> ErrorDetails {
+    pub(crate) fn details(&self) -> Option<ErrorDetails> {
         let error = match self {
-            ApiError::NonRetriable(e) => e.clone(),
-            ApiError::Retriable(e) => e.clone(),
+            ApiError::NonRetriable(e) => e,
+            ApiError::Retriable(e) => e,
+            ApiError::WrongNetwork => return None,
+            ApiError::UnavailableOffline => return None,
+            ApiError::UnsupportedOutputType(e) => e,
+            ApiError::InvalidAddress(e) => e,
+            ApiError::MilestonePruned(e) => e,
+            ApiError::OutputAlreadySpent(e) => e,
+            ApiError::NodeUnreachable(e) => e,
+            ApiError::BlockBeforeGenesis(e) => e,
+            ApiError::InvalidDustOutput(e) => e,
         };
-        ErrorDetails { error }
+        Some(ErrorDetails { error: error.clone() })
     }
 
     pub(crate) fn status_code(&self) -> StatusCode {
-        match self {
-            ApiError::NonRetriable(_) => StatusCode::BAD_REQUEST,
-            ApiError::Retriable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        match self.retriable() {
+            true => StatusCode::SERVICE_UNAVAILABLE,
+            false => StatusCode::BAD_REQUEST,
         }
     }
 
     pub(crate) fn all_errors() -> Vec<types::Error> {
         vec![
-            types::Error {
-                message: "non retriable error".to_string(),
-                code: 1,
-                retriable: false,
-                details: None,
-            },
-            types::Error {
-                message: "retriable error".to_string(),
-                code: 2,
-                retriable: true,
-                details: None,
-            },
+            ApiError::NonRetriable(String::new()),
+            ApiError::Retriable(String::new()),
+            ApiError::WrongNetwork,
+            ApiError::UnavailableOffline,
+            ApiError::UnsupportedOutputType(String::new()),
+            ApiError::InvalidAddress(String::new()),
+            ApiError::MilestonePruned(String::new()),
+            ApiError::OutputAlreadySpent(String::new()),
+            ApiError::NodeUnreachable(String::new()),
+            ApiError::BlockBeforeGenesis(String::new()),
+            ApiError::InvalidDustOutput(String::new()),
         ]
+        .into_iter()
+        .map(|api_error| types::Error {
+            message: api_error.message(),
+            code: api_error.code(),
+            retriable: api_error.retriable(),
+            details: None,
+        })
+        .collect()
     }
 
     pub fn into_error(self) -> types::Error {
@@ -70,7 +139,7 @@ impl ApiError {
             message: self.message(),
             code: self.code(),
             retriable: self.retriable(),
-            details: Some(self.details()),
+            details: self.details(),
         }
     }
 }
@@ -1,7 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{config::RosettaConfig, error::ApiError};
+use crate::{config::RosettaConfig, error::ApiError, metrics, state::ServerState};
 
 use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
@@ -15,6 +15,12 @@ pub fn with_rosetta_config(
     warp::any().map(move || options.clone())
 }
 
+pub fn with_server_state(
+    server_state: ServerState,
+) -> impl Filter<Extract = (ServerState,), Error = Infallible> + Clone {
+    warp::any().map(move || server_state.clone())
+}
+
 #[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct EmptyRequest;
@@ -25,21 +31,27 @@ pub fn with_empty_request() -> impl Filter<Extract = (EmptyRequest,), Error = In
 
 pub fn handle<'a, F, R, Req, Resp>(
     handler: F,
-) -> impl Fn(Req, RosettaConfig) -> BoxFuture<'static, Result<warp::reply::WithStatus<warp::reply::Json>, Infallible>> + Clone
+) -> impl Fn(
+    Req,
+    RosettaConfig,
+    ServerState,
+) -> BoxFuture<'static, Result<warp::reply::WithStatus<warp::reply::Json>, Infallible>>
+       + Clone
 where
-    F: FnOnce(Req, RosettaConfig) -> R + Clone + Copy + Send + 'static,
+    F: FnOnce(Req, RosettaConfig, ServerState) -> R + Clone + Copy + Send + 'static,
     R: Future<Output = Result<Resp, ApiError>> + Send,
     Req: Deserialize<'a> + Send + 'static,
     Resp: Serialize,
 {
-    move |request, options| {
+    move |request, options, server_state| {
         let fut = async move {
-            match handler(request, options).await {
+            match handler(request, options, server_state).await {
                 Ok(response) => Ok(warp::reply::with_status(
                     warp::reply::json(&response),
                     warp::http::StatusCode::OK,
                 )),
                 Err(api_error) => {
+                    metrics::record_api_error(&api_error);
                     let status = api_error.status_code();
                     Ok(warp::reply::with_status(
                         warp::reply::json(&api_error.into_error()),
@@ -0,0 +1,173 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::{get_confirmed_milestone, get_node_info, get_output, get_pruning_index, get_utxo_changes_from_pool},
+    config::RosettaConfig,
+    consts,
+    data::block::block_handler::{
+        block_response, build_block_transactions, conflicting_transactions, created_outputs_of_milestone,
+        messages_of_created_outputs, oldest_block_index, OutputCache,
+    },
+    error::ApiError,
+    state::ServerState,
+    types::{BlockIdentifier, BlockTransaction, PartialBlockIdentifier},
+};
+
+use bee_message::{address::Address, output::Output, prelude::OutputId};
+use futures::{stream, StreamExt, TryStreamExt};
+use iota_client::Client;
+use log::{error, warn};
+
+use std::{collections::HashMap, convert::TryFrom};
+
+/// Follows the confirmed milestones of the nodes and records every newly confirmed milestone in the local stores.
+pub async fn follow_milestones(server_state: ServerState, rosetta_config: RosettaConfig) {
+    let mut interval = tokio::time::interval(consts::MILESTONE_FOLLOWER_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = sync_milestones(&server_state, &rosetta_config).await {
+            warn!("can not follow confirmed milestones: {:?}", e);
+        }
+    }
+}
+
+/// Records all milestones that were confirmed since the last call. The first call starts at the oldest milestone that
+/// is still available, so the event log covers every block that can be requested.
+pub async fn sync_milestones(server_state: &ServerState, rosetta_config: &RosettaConfig) -> Result<(), ApiError> {
+    let client = server_state.client_pool.get().await?;
+    let confirmed_milestone = get_confirmed_milestone(&client).await?;
+
+    let next_index = match server_state.event_log.last_block_index() {
+        Some(last_index) => last_index + 1,
+        None => {
+            let pruning_index = get_pruning_index(&client).await?;
+            oldest_block_index(pruning_index + 1, pruning_index, rosetta_config, server_state)?
+        }
+    };
+
+    for milestone_index in next_index..=confirmed_milestone.index {
+        let partial_block_identifier = PartialBlockIdentifier {
+            index: Some(milestone_index),
+            hash: None,
+        };
+
+        // the block is built once; block_response also caches it if the block cache is enabled
+        let block = block_response(&partial_block_identifier, server_state, rosetta_config, &client)
+            .await?
+            .block;
+        let block_identifier = block.block_identifier;
+        let transactions = match block.other_transactions {
+            // very busy blocks only list their transactions, but the transaction index needs them as a whole
+            Some(_) => block_transactions(&block_identifier, server_state, rosetta_config, &client).await?,
+            None => block.transactions,
+        };
+
+        // the event is recorded last, it marks the milestone as completely processed
+        server_state
+            .transaction_index
+            .insert_block(&block_identifier, transactions);
+        server_state.event_log.block_added(block_identifier);
+    }
+
+    Ok(())
+}
+
+async fn block_transactions(
+    block_identifier: &BlockIdentifier,
+    server_state: &ServerState,
+    rosetta_config: &RosettaConfig,
+    iota_client: &Client,
+) -> Result<Vec<BlockTransaction>, ApiError> {
+    let created_outputs = created_outputs_of_milestone(block_identifier.index, server_state).await?;
+    let mut output_cache = OutputCache::new();
+    let messages = messages_of_created_outputs(created_outputs, &mut output_cache, iota_client).await?;
+    let conflicting = conflicting_transactions(block_identifier, iota_client).await?;
+    build_block_transactions(messages, conflicting, &mut output_cache, iota_client, rosetta_config).await
+}
+
+/// Follows the confirmed milestones with the balance store, independently of the other stores, since catching up from
+/// the snapshot's sep_index can take long.
+pub async fn follow_balances(server_state: ServerState) {
+    let mut interval = tokio::time::interval(consts::MILESTONE_FOLLOWER_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = sync_balances(&server_state).await {
+            error!("can not follow balances: {:?}", e);
+        }
+    }
+}
+
+/// Applies the balance diffs of all milestones that were confirmed since the ledger index of the balance store.
+pub async fn sync_balances(server_state: &ServerState) -> Result<(), ApiError> {
+    let balance_store = match &server_state.balance_store {
+        Some(balance_store) => balance_store,
+        None => return Ok(()),
+    };
+
+    let client = server_state.client_pool.get().await?;
+    let node_info = get_node_info(&client).await?;
+
+    let next_index = balance_store.ledger_index() + 1;
+    if next_index <= node_info.pruning_index {
+        return Err(ApiError::NonRetriable(format!(
+            "the balances can not catch up: the node pruned milestone {} already, use a snapshot with a sep_index of at \
+             least {}",
+            next_index, node_info.pruning_index
+        )));
+    }
+
+    for milestone_index in next_index..=node_info.confirmed_milestone_index {
+        let balance_diffs = balance_diffs_of_milestone(milestone_index, server_state, &client).await?;
+        balance_store.apply_milestone(milestone_index, balance_diffs)?;
+    }
+
+    Ok(())
+}
+
+async fn balance_diffs_of_milestone(
+    milestone_index: u32,
+    server_state: &ServerState,
+    iota_client: &Client,
+) -> Result<HashMap<Address, i64>, ApiError> {
+    let utxo_changes = get_utxo_changes_from_pool(milestone_index, &server_state.client_pool).await?;
+
+    let mut balance_diffs = HashMap::new();
+
+    let created_outputs = utxo_changes.created_outputs.iter().map(|id| (id, 1));
+    let consumed_outputs = utxo_changes.consumed_outputs.iter().map(|id| (id, -1));
+
+    let outputs = stream::iter(created_outputs.chain(consumed_outputs))
+        .map(|(output_id, sign)| async move {
+            let output_id = output_id
+                .parse::<OutputId>()
+                .map_err(|e| ApiError::NonRetriable(format!("can not parse output id: {}", e)))?;
+            get_output(output_id, iota_client)
+                .await
+                .map(|output_response| (output_response, sign))
+        })
+        .buffer_unordered(consts::MAX_CONCURRENT_NODE_REQUESTS)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    for (output_response, sign) in outputs {
+        let output = Output::try_from(&output_response.output)
+            .map_err(|e| ApiError::NonRetriable(format!("can not deserialize output: {}", e)))?;
+
+        let (address, amount) = match output {
+            Output::SignatureLockedSingle(o) => (*o.address(), o.amount()),
+            Output::SignatureLockedDustAllowance(o) => (*o.address(), o.amount()),
+            _ => {
+                return Err(ApiError::UnsupportedOutputType(
+                    "only signature locked outputs are supported".to_string(),
+                ))
+            }
+        };
+
+        *balance_diffs.entry(address).or_insert(0) += sign * amount as i64;
+    }
+
+    Ok(balance_diffs)
+}
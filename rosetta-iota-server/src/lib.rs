@@ -1,7 +1,14 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{config::RosettaMode, error::ApiError, types::NetworkIdentifier};
+use crate::{
+    client::watch_node_health,
+    config::RosettaMode,
+    error::ApiError,
+    follower::{follow_balances, follow_milestones},
+    state::ServerState,
+    types::NetworkIdentifier,
+};
 
 pub use config::RosettaConfig;
 
@@ -18,12 +25,21 @@ pub mod consts;
 pub mod data;
 pub mod error;
 pub mod filters;
+pub mod follower;
+pub mod metrics;
 pub mod operations;
+pub mod state;
 pub mod types;
 
 pub async fn run_server(config: RosettaConfig, shutdown: impl Future<Output = ()> + Send + 'static) {
     env_logger::init();
 
+    if let Some(quorum_size) = config.quorum_size {
+        if quorum_size == 0 || quorum_size > config.node_urls.len() {
+            panic!("quorum size must be between 1 and the number of node urls");
+        }
+    }
+
     let bind_addr = config
         .bind_addr
         .parse::<SocketAddr>()
@@ -31,19 +47,36 @@ pub async fn run_server(config: RosettaConfig, shutdown: impl Future<Output = ()
 
     info!("Listening on {}.", bind_addr.to_string());
     info!(
-        "BIND_ADDRESS {} NETWORK {} BECH32_HRP {} NODE_URL {} MODE {:#?}",
+        "BIND_ADDRESS {} NETWORK {} BECH32_HRP {} NODE_URLS {:?} QUORUM_SIZE {:?} MODE {:#?}",
         bind_addr.to_string(),
         config.network,
         config.bech32_hrp,
-        config.node_url,
+        config.node_urls,
+        config.quorum_size,
         config.mode
     );
 
-    let routes = data::network::routes(config.clone())
-        .or(data::block::routes(config.clone()))
-        .or(data::account::routes(config.clone()))
-        .or(construction::routes(config.clone()))
-        .recover(handle_rejection);
+    let server_state = ServerState::new(&config);
+
+    if !is_offline_mode_enabled(&config) {
+        tokio::spawn(watch_node_health(server_state.client_pool.clone()));
+        tokio::spawn(follow_milestones(server_state.clone(), config.clone()));
+        if server_state.balance_store.is_some() {
+            tokio::spawn(follow_balances(server_state.clone()));
+        }
+    }
+
+    let routes = data::network::routes(config.clone(), server_state.clone())
+        .or(data::block::routes(config.clone(), server_state.clone()))
+        .or(data::account::routes(config.clone(), server_state.clone()))
+        .or(data::call::routes(config.clone(), server_state.clone()))
+        .or(data::events::routes(config.clone(), server_state.clone()))
+        .or(data::mempool::routes(config.clone(), server_state.clone()))
+        .or(data::search::routes(config.clone(), server_state.clone()))
+        .or(construction::routes(config.clone(), server_state))
+        .or(metrics::routes())
+        .recover(handle_rejection)
+        .with(warp::log::custom(metrics::record_request));
 
     let (_, server) = warp::serve(routes).bind_with_graceful_shutdown(bind_addr, shutdown);
 
@@ -76,7 +109,7 @@ async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply, Infa
         retriable = api_error.retriable();
         status = api_error.status_code();
         code = api_error.code();
-        details = Some(api_error.details());
+        details = api_error.details();
     } else {
         error!("unexpected internal error: {:?}", err);
         message = "internal server error".to_string();
@@ -0,0 +1,106 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::ApiError;
+
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, Encoder, HistogramTimer, HistogramVec,
+    IntCounterVec, IntGaugeVec, TextEncoder,
+};
+use warp::Filter;
+
+lazy_static! {
+    static ref REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "rosetta_requests_total",
+        "Number of handled requests per route and status code",
+        &["route", "status"]
+    )
+    .expect("can not register metric");
+    static ref REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "rosetta_request_duration_seconds",
+        "Time spent handling requests per route",
+        &["route"]
+    )
+    .expect("can not register metric");
+    static ref API_ERRORS: IntCounterVec =
+        register_int_counter_vec!("rosetta_api_errors_total", "Number of returned API errors", &["error"])
+            .expect("can not register metric");
+    static ref NODE_CALL_DURATION: HistogramVec = register_histogram_vec!(
+        "rosetta_node_call_duration_seconds",
+        "Time spent waiting for the node per call",
+        &["call"]
+    )
+    .expect("can not register metric");
+    static ref MILESTONE_GAP: IntGaugeVec = register_int_gauge_vec!(
+        "rosetta_node_milestone_gap",
+        "Number of milestones between the latest and the confirmed milestone of a node",
+        &["node"]
+    )
+    .expect("can not register metric");
+}
+
+pub fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::get().and(warp::path!("metrics")).map(|| {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&prometheus::gather(), &mut buffer)
+            .expect("can not encode metrics");
+        String::from_utf8(buffer).expect("metrics are not valid UTF-8")
+    })
+}
+
+// paths of the served routes; requests to any other path are recorded as unmatched, so a client can not create a time
+// series per path
+const ROUTES: [&str; 21] = [
+    "/account/balance",
+    "/account/coins",
+    "/block",
+    "/block/transaction",
+    "/call",
+    "/construction/combine",
+    "/construction/derive",
+    "/construction/hash",
+    "/construction/metadata",
+    "/construction/parse",
+    "/construction/payloads",
+    "/construction/preprocess",
+    "/construction/submit",
+    "/events/blocks",
+    "/mempool",
+    "/mempool/transaction",
+    "/metrics",
+    "/network/list",
+    "/network/options",
+    "/network/status",
+    "/search/transactions",
+];
+
+pub fn record_request(info: warp::log::Info) {
+    let route = ROUTES
+        .iter()
+        .find(|route| **route == info.path())
+        .copied()
+        .unwrap_or("unmatched");
+
+    REQUESTS.with_label_values(&[route, info.status().as_str()]).inc();
+    REQUEST_DURATION
+        .with_label_values(&[route])
+        .observe(info.elapsed().as_secs_f64());
+}
+
+pub fn record_api_error(api_error: &ApiError) {
+    API_ERRORS.with_label_values(&[api_error.variant_name()]).inc();
+}
+
+/// Starts a timer which records the duration of a node call once it is dropped. Only calls that send a request to the
+/// node start a timer, so calls composed of others are not recorded twice.
+pub fn node_call_timer(call: &str) -> HistogramTimer {
+    NODE_CALL_DURATION.with_label_values(&[call]).start_timer()
+}
+
+pub fn set_milestone_gap(node_url: &str, confirmed_milestone_index: u32, latest_milestone_index: u32) {
+    MILESTONE_GAP
+        .with_label_values(&[node_url])
+        .set(latest_milestone_index as i64 - confirmed_milestone_index as i64);
+}
@@ -2,17 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    consts::iota_currency,
+    consts::{iota_currency, TREASURY_ACCOUNT},
     error::ApiError,
-    types::{AccountIdentifier, Amount, CoinAction, CoinChange, CoinIdentifier, Operation, OperationIdentifier},
+    types::{
+        AccountIdentifier, Amount, CoinAction, CoinChange, CoinIdentifier, Operation, OperationIdentifier,
+        OperationMetadata,
+    },
     RosettaConfig,
 };
-use bee_message::{address::Address, output::Output, prelude::OutputId};
+use bee_message::{address::Address, output::Output, payload::receipt::TailTransactionHash, prelude::OutputId};
 
 // operation types
 pub const INPUT: &str = "INPUT";
 pub const SIG_LOCKED_SINGLE_OUTPUT: &str = "SIG_LOCKED_SINGLE_OUTPUT";
 pub const SIG_LOCKED_DUST_ALLOWANCE_OUTPUT: &str = "SIG_LOCKED_DUST_ALLOWANCE_OUTPUT";
+pub const TREASURY_INPUT: &str = "TREASURY_INPUT";
+pub const TREASURY_OUTPUT: &str = "TREASURY_OUTPUT";
 
 // operation status
 pub const SUCCESS: &str = "Success";
@@ -27,6 +32,8 @@ pub fn operation_type_list() -> Vec<String> {
         INPUT.into(),
         SIG_LOCKED_SINGLE_OUTPUT.into(),
         SIG_LOCKED_DUST_ALLOWANCE_OUTPUT.into(),
+        TREASURY_INPUT.into(),
+        TREASURY_OUTPUT.into(),
     ]
 }
 
@@ -52,7 +59,11 @@ pub fn build_utxo_input_operation(
         Output::SignatureLockedDustAllowance(r) => match r.address() {
             Address::Ed25519(addr) => (r.amount(), *addr),
         },
-        _ => return Err(ApiError::NonRetriable("output type not supported".to_string())),
+        _ => {
+            return Err(ApiError::UnsupportedOutputType(
+                "only signature locked outputs are supported".to_string(),
+            ))
+        }
     };
 
     let account = AccountIdentifier {
@@ -72,7 +83,7 @@ pub fn build_utxo_input_operation(
         type_: INPUT.into(),
         status: match is_online {
             true => Some(SUCCESS.into()), // call coming from /data/block
-            false => None,                // call coming from /construction/parse
+            false => None,                // call coming from /construction/parse or /mempool/transaction
         },
         account: Some(account),
         amount: Some(amount),
@@ -82,6 +93,7 @@ pub fn build_utxo_input_operation(
             },
             coin_action: CoinAction::CoinSpent,
         }),
+        metadata: None,
     })
 }
 
@@ -99,7 +111,11 @@ pub fn build_utxo_output_operation(
         Output::SignatureLockedDustAllowance(r) => match r.address() {
             Address::Ed25519(addr) => (r.amount(), *addr),
         },
-        _ => return Err(ApiError::NonRetriable("output type not supported".to_string())),
+        _ => {
+            return Err(ApiError::UnsupportedOutputType(
+                "only signature locked outputs are supported".to_string(),
+            ))
+        }
     };
 
     let account = AccountIdentifier {
@@ -119,7 +135,11 @@ pub fn build_utxo_output_operation(
         type_: match output {
             Output::SignatureLockedSingle(_) => SIG_LOCKED_SINGLE_OUTPUT.into(),
             Output::SignatureLockedDustAllowance(_) => SIG_LOCKED_DUST_ALLOWANCE_OUTPUT.into(),
-            _ => return Err(ApiError::NonRetriable("output type not supported".to_string())),
+            _ => {
+                return Err(ApiError::UnsupportedOutputType(
+                    "only signature locked outputs are supported".to_string(),
+                ))
+            }
         },
         status: match is_online {
             true => Some(SUCCESS.into()),
@@ -133,5 +153,35 @@ pub fn build_utxo_output_operation(
             },
             coin_action: CoinAction::CoinCreated,
         }),
+        metadata: None,
     })
 }
+
+/// Builds an operation on the treasury account; the treasury is only changed by receipts of the legacy-network
+/// migration, so the operation always comes from a confirmed milestone.
+pub fn build_treasury_operation(type_: &str, amount: i64, operation_counter: usize) -> Operation {
+    Operation {
+        operation_identifier: OperationIdentifier {
+            index: operation_counter as u64,
+            network_index: None,
+        },
+        type_: type_.into(),
+        status: Some(SUCCESS.into()),
+        account: Some(AccountIdentifier {
+            address: TREASURY_ACCOUNT.into(),
+        }),
+        amount: Some(Amount {
+            value: amount.to_string(),
+            currency: iota_currency(),
+        }),
+        coin_change: None,
+        metadata: None,
+    }
+}
+
+/// Metadata of an output that was migrated from the legacy network.
+pub fn migrated_funds_metadata(tail_transaction_hash: &TailTransactionHash) -> OperationMetadata {
+    OperationMetadata {
+        tail_transaction_hash: Some(tail_transaction_hash.to_string()),
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::ClientPool,
+    config::RosettaConfig,
+    data::{
+        account::balance_store::BalanceStore,
+        block::{block_cache::BlockCache, block_hashes::BlockHashIndex, milestone_timestamps::MilestoneTimestamps},
+        events::event_log::EventLog,
+        mempool::mempool_snapshot::MempoolSnapshot,
+        search::transaction_index::TransactionIndex,
+    },
+};
+
+#[derive(Clone)]
+pub struct ServerState {
+    pub client_pool: ClientPool,
+    pub event_log: EventLog,
+    pub transaction_index: TransactionIndex,
+    pub balance_store: Option<BalanceStore>,
+    pub block_hashes: BlockHashIndex,
+    pub milestone_timestamps: MilestoneTimestamps,
+    pub block_cache: Option<BlockCache>,
+    pub mempool_snapshot: MempoolSnapshot,
+    pub genesis_index: u32,
+}
+
+impl ServerState {
+    pub fn new(rosetta_config: &RosettaConfig) -> Self {
+        let balance_store = rosetta_config.snapshot_dir.as_ref().map(|snapshot_dir| {
+            BalanceStore::from_snapshot_dir(snapshot_dir).expect("can not load the balances of the snapshot")
+        });
+
+        // the balances of the snapshot already include the milestone at its sep_index, so the chain starts after it
+        let genesis_index = rosetta_config
+            .genesis_index
+            .or_else(|| {
+                balance_store
+                    .as_ref()
+                    .map(|balance_store| balance_store.sep_index() + 1)
+            })
+            .unwrap_or(1);
+
+        Self {
+            client_pool: ClientPool::new(rosetta_config),
+            event_log: EventLog::new(genesis_index),
+            transaction_index: TransactionIndex::new(),
+            balance_store,
+            block_hashes: BlockHashIndex::new(),
+            milestone_timestamps: MilestoneTimestamps::new(),
+            block_cache: rosetta_config
+                .block_cache_dir
+                .as_ref()
+                .map(|block_cache_dir| BlockCache::open(block_cache_dir).expect("can not open the block cache")),
+            mempool_snapshot: MempoolSnapshot::new(),
+            genesis_index,
+        }
+    }
+}
@@ -49,6 +49,26 @@ pub struct Block {
     pub parent_block_identifier: BlockIdentifier,
     pub timestamp: u64,
     pub transactions: Vec<BlockTransaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_transactions: Option<Vec<TransactionIdentifier>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockEvent {
+    pub sequence: u64,
+    pub block_identifier: BlockIdentifier,
+    #[serde(rename = "type")]
+    pub type_: BlockEventType,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub enum BlockEventType {
+    #[serde(rename = "block_added")]
+    BlockAdded,
+    #[serde(rename = "block_removed")]
+    BlockRemoved,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -56,6 +76,21 @@ pub struct Block {
 pub struct Coin {
     pub coin_identifier: CoinIdentifier,
     pub amount: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CoinMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoinMetadata {
+    pub mempool_state: MempoolState,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolState {
+    PendingCreation,
+    PendingSpend,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -111,6 +146,15 @@ pub struct Operation {
     pub amount: Option<Amount>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub coin_change: Option<CoinChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<OperationMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperationMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tail_transaction_hash: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -151,6 +195,53 @@ pub struct SigningPayload {
 pub struct BlockTransaction {
     pub transaction_identifier: TransactionIdentifier,
     pub operations: Vec<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BlockTransactionMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockTransactionMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parents: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexation: Option<IndexationMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IndexationMetadata {
+    pub index: String,
+    pub data: String,
+}
+
+impl From<&IndexationPayload> for IndexationMetadata {
+    fn from(indexation: &IndexationPayload) -> Self {
+        Self {
+            index: hex::encode(indexation.index()),
+            data: hex::encode(indexation.data()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IndexedTransaction {
+    pub block_identifier: BlockIdentifier,
+    pub transaction: BlockTransaction,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub enum Operator {
+    #[serde(rename = "and")]
+    And,
+    #[serde(rename = "or")]
+    Or,
 }
 
 // Identifiers
@@ -248,10 +339,37 @@ pub struct SyncStatus {
 pub struct Version {
     pub rosetta_version: String,
     pub node_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<VersionMetadata>,
 }
 
 /// Self-defined objects
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionMetadata {
+    /// Only the transactions of these blocks can be found with /search/transactions, and only outputs spent in them
+    /// have a spending transaction in the `get_output` call method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexed_blocks: Option<IndexedBlocks>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IndexedBlocks {
+    pub oldest_index: u32,
+    pub latest_index: u32,
+}
+
+impl IndexedBlocks {
+    pub fn from_range((oldest_index, latest_index): (u32, u32)) -> Self {
+        Self {
+            oldest_index,
+            latest_index,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct UnsignedTransaction {
@@ -296,16 +414,77 @@ impl SignedTransaction {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PreprocessMetadata {
+    pub sender: Option<AccountIdentifier>,
+    pub input_selection_strategy: Option<InputSelectionStrategy>,
+    pub indexation: Option<IndexationMetadata>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputSelectionStrategy {
+    LargestFirst,
+    DustConsolidating,
+}
+
+impl Default for InputSelectionStrategy {
+    fn default() -> Self {
+        InputSelectionStrategy::LargestFirst
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct PreprocessOptions {
+    #[serde(default)]
     pub utxo_inputs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_selection: Option<InputSelectionOptions>,
+    /// The recipients of dust outputs, whose dust allowance state is needed to validate the transaction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dust_addresses: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexation: Option<IndexationMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct InputSelectionOptions {
+    pub sender: AccountIdentifier,
+    pub amount: u64,
+    pub strategy: InputSelectionStrategy,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ConstructionMetadata {
     pub utxo_inputs_metadata: HashMap<String, OutputResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_inputs: Option<SelectedInputs>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub dust_allowances: HashMap<String, DustAllowanceState>,
+    /// Indexation payload that is attached to the transaction; index and data are hex encoded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexation: Option<IndexationMetadata>,
+}
+
+/// Sum of the dust allowance outputs of an address and the number of its dust outputs.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DustAllowanceState {
+    pub dust_allowance: u64,
+    pub dust_outputs: u64,
+}
+
+/// Coins that `/construction/metadata` selected from the sender, and the change that goes back to the sender.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SelectedInputs {
+    pub sender: AccountIdentifier,
+    pub coins: Vec<CoinIdentifier>,
+    pub change: u64,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
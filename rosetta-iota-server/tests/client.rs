@@ -0,0 +1,149 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{
+        default_rosetta_config, BAD_REQUEST_MILESTONE_INDEX, DUMMY_NODE_BIND_ADDR, FAILING_MILESTONE_INDEX,
+        MISSING_OUTPUT_ID, SECOND_DUMMY_NODE_BIND_ADDR, UNREACHABLE_NODE_BIND_ADDR, VALID_BECH32_ADDRESS_WITH_BALANCE,
+    },
+    dummy_node::start_dummy_node_at,
+};
+
+use rosetta_iota_server::{
+    client::{
+        build_client, get_balance_of_address_from_pool, get_milestone, get_node_info, get_output,
+        get_utxo_changes_from_pool, ClientPool,
+    },
+    error::ApiError,
+    RosettaConfig,
+};
+
+use bee_message::output::OutputId;
+
+use serial_test::serial;
+
+fn rosetta_config_with_nodes(bind_addrs: &[&str], quorum_size: Option<usize>) -> RosettaConfig {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.node_urls = bind_addrs
+        .iter()
+        .map(|bind_addr| format!("http://{}", bind_addr))
+        .collect();
+    rosetta_config.quorum_size = quorum_size;
+    rosetta_config
+}
+
+#[tokio::test]
+#[serial]
+async fn fails_over_to_reachable_node() {
+    let rosetta_config = rosetta_config_with_nodes(&[UNREACHABLE_NODE_BIND_ADDR, DUMMY_NODE_BIND_ADDR], None);
+    let client_pool = ClientPool::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node_at(DUMMY_NODE_BIND_ADDR).await;
+    let response = get_balance_of_address_from_pool(VALID_BECH32_ADDRESS_WITH_BALANCE, &client_pool).await;
+    dummy_node.shutdown().await;
+
+    assert_eq!(20651169480, response.unwrap().balance);
+}
+
+#[tokio::test]
+#[serial]
+async fn no_reachable_node() {
+    let rosetta_config = rosetta_config_with_nodes(&[UNREACHABLE_NODE_BIND_ADDR], None);
+    let client_pool = ClientPool::new(&rosetta_config);
+
+    let error = client_pool.get().await.err().unwrap();
+
+    assert!(error.retriable());
+}
+
+#[tokio::test]
+#[serial]
+async fn quorum_reached() {
+    let rosetta_config = rosetta_config_with_nodes(&[DUMMY_NODE_BIND_ADDR, SECOND_DUMMY_NODE_BIND_ADDR], Some(2));
+    let client_pool = ClientPool::new(&rosetta_config);
+
+    let first_dummy_node = start_dummy_node_at(DUMMY_NODE_BIND_ADDR).await;
+    let second_dummy_node = start_dummy_node_at(SECOND_DUMMY_NODE_BIND_ADDR).await;
+    let balance = get_balance_of_address_from_pool(VALID_BECH32_ADDRESS_WITH_BALANCE, &client_pool).await;
+    let utxo_changes = get_utxo_changes_from_pool(1438448, &client_pool).await;
+    first_dummy_node.shutdown().await;
+    second_dummy_node.shutdown().await;
+
+    // the nodes are at different ledger indexes, but agree on the balance
+    let balance = balance.unwrap();
+    assert_eq!(20651169480, balance.balance);
+    assert_eq!(1438441, balance.ledger_index);
+    assert_eq!(1, utxo_changes.unwrap().created_outputs.len());
+}
+
+#[tokio::test]
+#[serial]
+async fn quorum_not_reachable() {
+    let rosetta_config = rosetta_config_with_nodes(&[DUMMY_NODE_BIND_ADDR, UNREACHABLE_NODE_BIND_ADDR], Some(2));
+    let client_pool = ClientPool::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node_at(DUMMY_NODE_BIND_ADDR).await;
+    let response = get_balance_of_address_from_pool(VALID_BECH32_ADDRESS_WITH_BALANCE, &client_pool).await;
+    dummy_node.shutdown().await;
+
+    assert!(response.err().unwrap().retriable());
+}
+
+#[tokio::test]
+#[serial]
+async fn not_found_is_not_retriable() {
+    let rosetta_config = default_rosetta_config();
+    let client = build_client(&rosetta_config.node_urls[0], &rosetta_config)
+        .await
+        .unwrap();
+
+    let dummy_node = start_dummy_node_at(DUMMY_NODE_BIND_ADDR).await;
+    let response = get_output(MISSING_OUTPUT_ID.parse::<OutputId>().unwrap(), &client).await;
+    dummy_node.shutdown().await;
+
+    assert!(!response.err().unwrap().retriable());
+}
+
+#[tokio::test]
+#[serial]
+async fn bad_request_is_not_retriable() {
+    let rosetta_config = default_rosetta_config();
+    let client = build_client(&rosetta_config.node_urls[0], &rosetta_config)
+        .await
+        .unwrap();
+
+    let dummy_node = start_dummy_node_at(DUMMY_NODE_BIND_ADDR).await;
+    let response = get_milestone(BAD_REQUEST_MILESTONE_INDEX, &client).await;
+    dummy_node.shutdown().await;
+
+    assert!(!response.err().unwrap().retriable());
+}
+
+#[tokio::test]
+#[serial]
+async fn server_error_is_retriable() {
+    let rosetta_config = default_rosetta_config();
+    let client = build_client(&rosetta_config.node_urls[0], &rosetta_config)
+        .await
+        .unwrap();
+
+    let dummy_node = start_dummy_node_at(DUMMY_NODE_BIND_ADDR).await;
+    let response = get_milestone(FAILING_MILESTONE_INDEX, &client).await;
+    dummy_node.shutdown().await;
+
+    assert!(response.err().unwrap().retriable());
+}
+
+#[tokio::test]
+#[serial]
+async fn refused_connection_is_retriable() {
+    let rosetta_config = default_rosetta_config();
+    let client = build_client(&format!("http://{}", UNREACHABLE_NODE_BIND_ADDR), &rosetta_config)
+        .await
+        .unwrap();
+
+    let error = get_node_info(&client).await.err().unwrap();
+
+    assert!(error.retriable());
+    assert!(matches!(error, ApiError::NodeUnreachable(_)));
+}
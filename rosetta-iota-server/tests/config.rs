@@ -4,21 +4,51 @@
 use rosetta_iota_server::{config::RosettaMode, RosettaConfig};
 
 pub const DUMMY_NODE_BIND_ADDR: &str = "127.0.0.1:12345";
+pub const SECOND_DUMMY_NODE_BIND_ADDR: &str = "127.0.0.1:12346";
+pub const UNREACHABLE_NODE_BIND_ADDR: &str = "127.0.0.1:12347";
 
 pub const VALID_NETWORK: &str = "chrysalis-mainnet";
 pub const VALID_BLOCKCHAIN: &str = "iota";
 pub const VALID_BECH32_ADDRESS_WITH_BALANCE: &str = "iota1qp6gwwy7rruk0d3j9fqzcxnfrstfedk2m65jst2tx7xmkad4agjc5r7ptjz";
 
+// owns the dust outputs DUST_TRANSACTION_ID:0 and :1 of 1 and 500000 IOTA and the dust allowance output :2 of 1 Mi
+pub const DUST_ADDRESS: &str = "iota1qq2yjks40a9lhqhfnkkjdxpretr7a2wz30m6ar3a3tpawjx47ru8z5g0x6j";
+pub const DUST_TRANSACTION_ID: &str = "3cc7ef894d9e4465a1b37c11f13c39a420b9ede778f05adb638d1eebcee4d303";
+
+pub const MISSING_OUTPUT_ID: &str = "00000000000000000000000000000000000000000000000000000000000000000000";
+pub const CONFLICTING_MESSAGE_ID: &str = "b1c4ad5c0b8f8ff1a1e8f19e16c6fa4cba0d7f4f31d2e0c3f45a6b7c8d9e0f12";
+pub const CONFLICTING_TRANSACTION_ID: &str = "08c41b13e895eb11db3f640669dc51225745dac7ea77cf8f4e28c5a925f74834";
+pub const PENDING_MESSAGE_ID: &str = "70a9a9bc408121b766cc20d9a5b8dba0829e41244c500b2d04cf34f1f20f4621";
+// spends the coin of VALID_BECH32_ADDRESS_WITH_BALANCE and returns most of it to the address
+pub const PENDING_SPEND_MESSAGE_ID: &str = "6e20f4e864621a5537c456198fa8154c947c6097fba306af780bd055b14654ba";
+pub const PENDING_SPEND_TRANSACTION_ID: &str = "ed1f84ece1e663335a719d3e275ad32772952da2dd10f2362483a5a8b26d5f69";
+// the only transaction of milestone INDEXATION_MILESTONE_INDEX, its essence carries an indexation payload
+pub const INDEXATION_MILESTONE_INDEX: u32 = 1438449;
+pub const INDEXATION_MESSAGE_ID: &str = "3110fe8fb04a26c808cb1e61950959232a2d0b406673f094f00753e600cb3548";
+pub const INDEXATION_TRANSACTION_ID: &str = "a99dc9b2dbcc3485501a4fac311f364bcaa9b7350c0ce385af17f222bffdb95b";
+// references the transactions of INDEXATION_MESSAGE_ID and PENDING_MESSAGE_ID, and a conflicting transaction that
+// spends MISSING_OUTPUT_ID
+pub const MULTI_TRANSACTION_MILESTONE_INDEX: u32 = 1438450;
+pub const MISSING_INPUT_MESSAGE_ID: &str = "008035cf0590d1d9dd167bacc8590b24cb578b131bf39162c16280403214b964";
+pub const MISSING_INPUT_TRANSACTION_ID: &str = "19b1095414cfd253e9e8ada747fb715e11c2657e59bc4a238f7edd57d72a0af3";
+pub const FAILING_MILESTONE_INDEX: u32 = 2;
+pub const BAD_REQUEST_MILESTONE_INDEX: u32 = 3;
+
 pub const WRONG_NETWORK: &str = "xyz";
 pub const WRONG_BLOCKCHAIN: &str = "ethereum";
 pub const WRONG_ADDRESS_FORMAT: &str = "abc";
 
 pub fn default_rosetta_config() -> RosettaConfig {
     RosettaConfig {
-        node_url: format!("http://{}", DUMMY_NODE_BIND_ADDR),
+        node_urls: vec![format!("http://{}", DUMMY_NODE_BIND_ADDR)],
+        quorum_size: None,
         network: VALID_NETWORK.to_string(),
         bech32_hrp: "iota".to_string(),
         mode: RosettaMode::Online,
         bind_addr: "0.0.0.0:3030".to_string(),
+        snapshot_dir: None,
+        block_cache_dir: None,
+        genesis_index: None,
+        oldest_index: None,
     }
 }
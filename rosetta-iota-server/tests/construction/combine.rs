@@ -0,0 +1,146 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{config::default_rosetta_config, construction::payloads::payloads_request};
+
+use rosetta_iota_server::{
+    construction::{
+        combine::{combine, ConstructionCombineRequest},
+        encoding::deserialize_signed_transaction,
+        parse::{parse, ConstructionParseRequest},
+        payloads::{payloads, ConstructionPayloadsResponse},
+    },
+    state::ServerState,
+    types::{CurveType, PublicKey, Signature, SignatureType},
+};
+
+use bee_message::prelude::*;
+use serial_test::serial;
+
+async fn unsigned_transaction(server_state: &ServerState) -> ConstructionPayloadsResponse {
+    payloads(payloads_request(), default_rosetta_config(), server_state.clone())
+        .await
+        .unwrap()
+}
+
+fn combine_request(payloads_response: &ConstructionPayloadsResponse, signatures: usize) -> ConstructionCombineRequest {
+    let signing_payload = &payloads_response.payloads[0];
+    let signature = Signature {
+        signing_payload: signing_payload.clone(),
+        public_key: PublicKey {
+            hex_bytes: "29c4bd3a9b2fd2d4f75e7d2e8d3d8e9c9a4fa5c1e1d4f4b1f0fc1e7b0e5f3a11".to_string(),
+            curve_type: CurveType::Edwards25519,
+        },
+        signature_type: SignatureType::Edwards25519,
+        hex_bytes: "00".repeat(64),
+    };
+    ConstructionCombineRequest {
+        network_identifier: payloads_request().network_identifier,
+        unsigned_transaction: payloads_response.unsigned_transaction.clone(),
+        signatures: vec![signature; signatures],
+    }
+}
+
+async fn signers_of(signed_transaction: String, server_state: &ServerState) -> usize {
+    let parse_request = ConstructionParseRequest {
+        network_identifier: payloads_request().network_identifier,
+        signed: true,
+        transaction: signed_transaction,
+    };
+    let parse_response = parse(parse_request, default_rosetta_config(), server_state.clone())
+        .await
+        .unwrap();
+    assert_eq!(5, parse_response.operations.len());
+    parse_response.account_identifier_signers.unwrap().len()
+}
+
+#[tokio::test]
+#[serial]
+async fn one_signature_unlocks_all_inputs_of_address() {
+    let server_state = ServerState::new(&default_rosetta_config());
+    let payloads_response = unsigned_transaction(&server_state).await;
+
+    let response = combine(
+        combine_request(&payloads_response, 1),
+        default_rosetta_config(),
+        server_state.clone(),
+    )
+    .await
+    .unwrap();
+
+    // a signature unlock block for the first input and a reference unlock block for the second one
+    let signed_transaction = deserialize_signed_transaction(&response.signed_transaction).unwrap();
+    let unlock_blocks = signed_transaction.transaction().unlock_blocks();
+    assert_eq!(2, unlock_blocks.len());
+    assert!(matches!(unlock_blocks[0], UnlockBlock::Signature(_)));
+    assert!(matches!(&unlock_blocks[1], UnlockBlock::Reference(reference) if reference.index() == 0));
+
+    assert_eq!(1, signers_of(response.signed_transaction, &server_state).await);
+}
+
+#[tokio::test]
+#[serial]
+async fn signature_per_input() {
+    let server_state = ServerState::new(&default_rosetta_config());
+    let payloads_response = unsigned_transaction(&server_state).await;
+
+    let response = combine(
+        combine_request(&payloads_response, 2),
+        default_rosetta_config(),
+        server_state.clone(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(1, signers_of(response.signed_transaction, &server_state).await);
+}
+
+#[tokio::test]
+#[serial]
+async fn missing_signature() {
+    let server_state = ServerState::new(&default_rosetta_config());
+    let payloads_response = unsigned_transaction(&server_state).await;
+
+    let error = combine(
+        combine_request(&payloads_response, 0),
+        default_rosetta_config(),
+        server_state,
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn signature_of_address_without_input() {
+    let server_state = ServerState::new(&default_rosetta_config());
+    let payloads_response = unsigned_transaction(&server_state).await;
+
+    let mut request = combine_request(&payloads_response, 2);
+    request.signatures[1].signing_payload.account_identifier.address =
+        "atoi1qpmppfmvwlg5qjkwd8084ceh0emw6y9gegpmesn2vvrlacfep834wyqsxww".to_string();
+
+    let error = combine(request, default_rosetta_config(), server_state)
+        .await
+        .unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn different_signatures_of_address() {
+    let server_state = ServerState::new(&default_rosetta_config());
+    let payloads_response = unsigned_transaction(&server_state).await;
+
+    let mut request = combine_request(&payloads_response, 2);
+    request.signatures[1].hex_bytes = "01".repeat(64);
+
+    let error = combine(request, default_rosetta_config(), server_state)
+        .await
+        .unwrap_err();
+
+    assert_eq!(1, error.code());
+}
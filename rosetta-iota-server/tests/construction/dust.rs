@@ -0,0 +1,86 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use rosetta_iota_server::{construction::dust::validate_dust, types::DustAllowanceState};
+
+use bee_message::prelude::*;
+
+use std::collections::HashMap;
+
+fn address(byte: u8) -> Address {
+    Address::Ed25519(Ed25519Address::new([byte; 32]))
+}
+
+fn single(byte: u8, amount: u64) -> Output {
+    Output::SignatureLockedSingle(SignatureLockedSingleOutput::new(address(byte), amount).unwrap())
+}
+
+fn dust_allowance(byte: u8, amount: u64) -> Output {
+    Output::SignatureLockedDustAllowance(SignatureLockedDustAllowanceOutput::new(address(byte), amount).unwrap())
+}
+
+#[test]
+fn dust_without_allowance() {
+    let error = validate_dust(&[single(1, 500_000)], &[], &HashMap::new(), "iota").unwrap_err();
+
+    assert_eq!(11, error.code());
+}
+
+#[test]
+fn dust_with_allowance_of_same_transaction() {
+    validate_dust(
+        &[single(1, 500_000), dust_allowance(1, 1_000_000)],
+        &[],
+        &HashMap::new(),
+        "iota",
+    )
+    .unwrap();
+}
+
+#[test]
+fn allowance_covers_ten_dust_outputs() {
+    let mut dust_allowances = HashMap::new();
+    dust_allowances.insert(
+        address(1),
+        DustAllowanceState {
+            dust_allowance: 1_000_000,
+            dust_outputs: 9,
+        },
+    );
+
+    validate_dust(&[single(1, 1)], &[], &dust_allowances, "iota").unwrap();
+    validate_dust(&[single(1, 1), single(1, 1)], &[], &dust_allowances, "iota").unwrap_err();
+}
+
+#[test]
+fn consuming_allowance_invalidates_dust() {
+    let mut dust_allowances = HashMap::new();
+    dust_allowances.insert(
+        address(1),
+        DustAllowanceState {
+            dust_allowance: 1_000_000,
+            dust_outputs: 1,
+        },
+    );
+
+    let error = validate_dust(
+        &[single(2, 1_000_000)],
+        &[dust_allowance(1, 1_000_000)],
+        &dust_allowances,
+        "iota",
+    )
+    .unwrap_err();
+
+    assert_eq!(11, error.code());
+}
+
+#[test]
+fn regular_outputs_need_no_allowance() {
+    validate_dust(
+        &[single(1, 1_000_000)],
+        &[single(2, 1_000_000)],
+        &HashMap::new(),
+        "iota",
+    )
+    .unwrap();
+}
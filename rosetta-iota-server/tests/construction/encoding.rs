@@ -0,0 +1,159 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{config::default_rosetta_config, construction::payloads::payloads_request};
+
+use rosetta_iota_server::{
+    construction::{
+        combine::{combine, ConstructionCombineRequest},
+        encoding::*,
+        payloads::payloads,
+    },
+    state::ServerState,
+    types::{CurveType, PublicKey, Signature, SignatureType, UnsignedTransaction},
+};
+
+use serial_test::serial;
+
+use std::collections::HashMap;
+
+async fn unsigned_transaction() -> String {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+    payloads(payloads_request(), rosetta_config, server_state)
+        .await
+        .unwrap()
+        .unsigned_transaction
+}
+
+async fn signed_transaction() -> String {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+    let payloads_response = payloads(payloads_request(), rosetta_config.clone(), server_state.clone())
+        .await
+        .unwrap();
+    let request = ConstructionCombineRequest {
+        network_identifier: payloads_request().network_identifier,
+        unsigned_transaction: payloads_response.unsigned_transaction,
+        signatures: vec![Signature {
+            signing_payload: payloads_response.payloads[0].clone(),
+            public_key: PublicKey {
+                hex_bytes: "29c4bd3a9b2fd2d4f75e7d2e8d3d8e9c9a4fa5c1e1d4f4b1f0fc1e7b0e5f3a11".to_string(),
+                curve_type: CurveType::Edwards25519,
+            },
+            signature_type: SignatureType::Edwards25519,
+            hex_bytes: "00".repeat(64),
+        }],
+    };
+    combine(request, rosetta_config, server_state)
+        .await
+        .unwrap()
+        .signed_transaction
+}
+
+#[tokio::test]
+#[serial]
+async fn unsigned_transaction_round_trip() {
+    let encoded = unsigned_transaction().await;
+
+    let unsigned_transaction = deserialize_unsigned_transaction(&encoded).unwrap();
+
+    assert_eq!(2, unsigned_transaction.inputs_metadata().len());
+    assert_eq!(encoded, serialize_unsigned_transaction(&unsigned_transaction).unwrap());
+}
+
+#[tokio::test]
+#[serial]
+async fn signed_transaction_round_trip() {
+    let encoded = signed_transaction().await;
+
+    let signed_transaction = deserialize_signed_transaction(&encoded).unwrap();
+
+    assert_eq!(2, signed_transaction.inputs_metadata().len());
+    assert_eq!(encoded, serialize_signed_transaction(&signed_transaction).unwrap());
+}
+
+#[tokio::test]
+#[serial]
+async fn input_without_metadata() {
+    let encoded = unsigned_transaction().await;
+    let unsigned_transaction = deserialize_unsigned_transaction(&encoded).unwrap();
+    let unsigned_transaction = UnsignedTransaction::new(unsigned_transaction.essence().clone(), HashMap::new());
+
+    let encoded = serialize_unsigned_transaction(&unsigned_transaction).unwrap();
+    // a 0 byte for each of the two inputs
+    assert!(encoded.ends_with("0000"));
+
+    let decoded = deserialize_unsigned_transaction(&encoded).unwrap();
+
+    assert!(decoded.inputs_metadata().is_empty());
+    assert_eq!(encoded, serialize_unsigned_transaction(&decoded).unwrap());
+}
+
+#[tokio::test]
+#[serial]
+async fn malformed_input_metadata() {
+    let encoded = unsigned_transaction().await;
+    let unsigned_transaction = deserialize_unsigned_transaction(&encoded).unwrap();
+    let mut inputs_metadata = unsigned_transaction.inputs_metadata().clone();
+    for input_metadata in inputs_metadata.values_mut() {
+        input_metadata.message_id = "invalid".to_string();
+    }
+    let unsigned_transaction = UnsignedTransaction::new(unsigned_transaction.essence().clone(), inputs_metadata);
+
+    let error = serialize_unsigned_transaction(&unsigned_transaction).unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn legacy_json_encoding() {
+    let encoded = unsigned_transaction().await;
+    let unsigned_transaction = deserialize_unsigned_transaction(&encoded).unwrap();
+    let legacy = hex::encode(serde_json::to_string(&unsigned_transaction).unwrap());
+
+    let decoded = deserialize_unsigned_transaction(&legacy).unwrap();
+
+    assert_eq!(encoded, serialize_unsigned_transaction(&decoded).unwrap());
+    assert!(encoded.len() < legacy.len());
+
+    let encoded = signed_transaction().await;
+    let signed_transaction = deserialize_signed_transaction(&encoded).unwrap();
+    let legacy = hex::encode(serde_json::to_string(&signed_transaction).unwrap());
+
+    let decoded = deserialize_signed_transaction(&legacy).unwrap();
+
+    assert_eq!(encoded, serialize_signed_transaction(&decoded).unwrap());
+}
+
+#[tokio::test]
+#[serial]
+async fn wrong_kind_of_transaction() {
+    let encoded = signed_transaction().await;
+
+    let error = deserialize_unsigned_transaction(&encoded).unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn unsupported_version() {
+    let mut encoded = unsigned_transaction().await;
+    encoded.replace_range(0..2, "02");
+
+    let error = deserialize_unsigned_transaction(&encoded).unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn trailing_bytes() {
+    let encoded = format!("{}00", unsigned_transaction().await);
+
+    let error = deserialize_unsigned_transaction(&encoded).unwrap_err();
+
+    assert_eq!(1, error.code());
+}
@@ -0,0 +1,100 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use rosetta_iota_server::{
+    construction::input_selection::{select_inputs, UnspentCoin},
+    types::InputSelectionStrategy,
+};
+
+fn coins(amounts: &[u64]) -> Vec<UnspentCoin> {
+    amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| UnspentCoin {
+            output_id: format!("{:068x}", i),
+            amount: *amount,
+        })
+        .collect()
+}
+
+fn selected_amounts(amounts: &[u64], amount: u64, strategy: InputSelectionStrategy) -> (Vec<u64>, u64) {
+    let selection = select_inputs(coins(amounts), amount, strategy).unwrap();
+    (
+        selection.coins.iter().map(|coin| coin.amount).collect(),
+        selection.change,
+    )
+}
+
+#[test]
+fn largest_first() {
+    assert_eq!(
+        (vec![5_000_000], 2_000_000),
+        selected_amounts(
+            &[1_000_000, 5_000_000, 2_000_000],
+            3_000_000,
+            InputSelectionStrategy::LargestFirst
+        )
+    );
+    assert_eq!(
+        (vec![5_000_000, 2_000_000], 0),
+        selected_amounts(
+            &[1_000_000, 5_000_000, 2_000_000],
+            7_000_000,
+            InputSelectionStrategy::LargestFirst
+        )
+    );
+}
+
+#[test]
+fn change_never_becomes_dust() {
+    // 5 Mi alone would leave 500 Ki of change
+    assert_eq!(
+        (vec![5_000_000, 2_000_000], 2_500_000),
+        selected_amounts(&[2_000_000, 5_000_000], 4_500_000, InputSelectionStrategy::LargestFirst)
+    );
+    assert!(select_inputs(coins(&[5_000_000]), 4_500_000, InputSelectionStrategy::LargestFirst).is_err());
+}
+
+#[test]
+fn dust_consolidating() {
+    assert_eq!(
+        (vec![600_000, 400_000, 5_000_000], 3_000_000),
+        selected_amounts(
+            &[400_000, 5_000_000, 600_000],
+            3_000_000,
+            InputSelectionStrategy::DustConsolidating
+        )
+    );
+}
+
+#[test]
+fn insufficient_funds() {
+    assert!(select_inputs(
+        coins(&[1_000_000, 2_000_000]),
+        4_000_000,
+        InputSelectionStrategy::LargestFirst
+    )
+    .is_err());
+}
+
+#[test]
+fn amount_overflow() {
+    // the amount plus the dust threshold of the change overflows
+    assert_eq!(
+        1,
+        select_inputs(coins(&[u64::MAX]), u64::MAX - 1, InputSelectionStrategy::LargestFirst)
+            .unwrap_err()
+            .code()
+    );
+    // the sum of the coins overflows
+    assert_eq!(
+        1,
+        select_inputs(
+            coins(&[u64::MAX / 3 + 1; 3]),
+            u64::MAX - 2_000_000,
+            InputSelectionStrategy::LargestFirst
+        )
+        .unwrap_err()
+        .code()
+    );
+}
@@ -1,8 +1,161 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{
+    config::{DUST_ADDRESS, DUST_TRANSACTION_ID, VALID_BECH32_ADDRESS_WITH_BALANCE, VALID_BLOCKCHAIN, VALID_NETWORK},
+    test_request, Request,
+};
+
+use rosetta_iota_server::{
+    construction::metadata::ConstructionMetadataRequest,
+    types::{
+        AccountIdentifier, DustAllowanceState, InputSelectionOptions, InputSelectionStrategy, NetworkIdentifier,
+        PreprocessOptions,
+    },
+};
+
 use serial_test::serial;
 
+const SELECTED_OUTPUT_ID: &str = "d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000";
+
+fn metadata_request(utxo_inputs: Vec<String>, dust_addresses: Vec<String>) -> ConstructionMetadataRequest {
+    ConstructionMetadataRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        options: PreprocessOptions {
+            utxo_inputs,
+            input_selection: None,
+            dust_addresses,
+            indexation: None,
+        },
+    }
+}
+
 #[tokio::test]
 #[serial]
 async fn valid_request() {}
+
+#[tokio::test]
+#[serial]
+async fn selects_inputs_of_sender() {
+    let request = ConstructionMetadataRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        options: PreprocessOptions {
+            utxo_inputs: vec![],
+            input_selection: Some(InputSelectionOptions {
+                sender: AccountIdentifier {
+                    address: VALID_BECH32_ADDRESS_WITH_BALANCE.to_string(),
+                },
+                amount: 10000000000,
+                strategy: InputSelectionStrategy::LargestFirst,
+            }),
+            dust_addresses: vec![VALID_BECH32_ADDRESS_WITH_BALANCE.to_string()],
+            indexation: None,
+        },
+    };
+
+    let response = test_request(Request::ConstructionMetadata(request))
+        .await
+        .unwrap_construction_metadata_response()
+        .unwrap();
+
+    let selected_inputs = response.metadata.selected_inputs.unwrap();
+    assert_eq!(VALID_BECH32_ADDRESS_WITH_BALANCE, selected_inputs.sender.address);
+    assert_eq!(1, selected_inputs.coins.len());
+    assert_eq!(SELECTED_OUTPUT_ID, selected_inputs.coins[0].identifier);
+    assert_eq!(10651169480, selected_inputs.change);
+    assert!(response.metadata.utxo_inputs_metadata.contains_key(SELECTED_OUTPUT_ID));
+
+    // the only output of the address is not a dust output
+    assert_eq!(
+        DustAllowanceState::default(),
+        response.metadata.dust_allowances[VALID_BECH32_ADDRESS_WITH_BALANCE]
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn insufficient_funds_of_sender() {
+    let request = ConstructionMetadataRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        options: PreprocessOptions {
+            utxo_inputs: vec![],
+            input_selection: Some(InputSelectionOptions {
+                sender: AccountIdentifier {
+                    address: VALID_BECH32_ADDRESS_WITH_BALANCE.to_string(),
+                },
+                amount: 30000000000,
+                strategy: InputSelectionStrategy::DustConsolidating,
+            }),
+            dust_addresses: vec![],
+            indexation: None,
+        },
+    };
+
+    test_request(Request::ConstructionMetadata(request))
+        .await
+        .unwrap_construction_metadata_response()
+        .unwrap_err();
+}
+
+#[tokio::test]
+#[serial]
+async fn dust_allowance_state_of_recipient() {
+    let request = metadata_request(vec![], vec![DUST_ADDRESS.to_string()]);
+
+    let response = test_request(Request::ConstructionMetadata(request))
+        .await
+        .unwrap_construction_metadata_response()
+        .unwrap();
+
+    assert_eq!(
+        DustAllowanceState {
+            dust_allowance: 1000000,
+            dust_outputs: 2,
+        },
+        response.metadata.dust_allowances[DUST_ADDRESS]
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn dust_allowance_state_of_spent_dust_allowance_owner() {
+    // the address is not listed, but spending its dust allowance output may invalidate its dust outputs
+    let request = metadata_request(vec![format!("{}0200", DUST_TRANSACTION_ID)], vec![]);
+
+    let response = test_request(Request::ConstructionMetadata(request))
+        .await
+        .unwrap_construction_metadata_response()
+        .unwrap();
+
+    assert_eq!(1, response.metadata.dust_allowances.len());
+    assert_eq!(
+        DustAllowanceState {
+            dust_allowance: 1000000,
+            dust_outputs: 2,
+        },
+        response.metadata.dust_allowances[DUST_ADDRESS]
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn no_dust_allowance_state_without_dust() {
+    // the input is a regular output, so its owner can not end up with too many dust outputs
+    let request = metadata_request(vec![SELECTED_OUTPUT_ID.to_string()], vec![]);
+
+    let response = test_request(Request::ConstructionMetadata(request))
+        .await
+        .unwrap_construction_metadata_response()
+        .unwrap();
+
+    assert!(response.metadata.dust_allowances.is_empty());
+}
@@ -1,6 +1,11 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod combine;
 mod derive;
+mod dust;
+mod encoding;
+mod input_selection;
 mod metadata;
+mod payloads;
 mod preprocess;
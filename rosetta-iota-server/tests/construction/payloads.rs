@@ -0,0 +1,242 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::default_rosetta_config;
+
+use rosetta_iota_server::{
+    construction::{
+        parse::{parse, ConstructionParseRequest},
+        payloads::{payloads, ConstructionPayloadsRequest},
+    },
+    state::ServerState,
+    types::IndexationMetadata,
+};
+
+use serial_test::serial;
+
+pub fn payloads_request() -> ConstructionPayloadsRequest {
+    serde_json::from_str(
+        r#"
+        {
+           "network_identifier":{
+              "blockchain":"iota",
+              "network":"chrysalis-mainnet"
+           },
+           "operations":[
+              {
+                 "operation_identifier":{
+                    "index":0,
+                    "network_index":0
+                 },
+                 "type":"INPUT",
+                 "account":{
+                    "address":"atoi1qr49znuapruu3fhwcfd4vsq2y3a0l9k8zc6pv6ak70g4hd9jq8fr2lqf6et"
+                 },
+                 "amount":{
+                    "value":"-6000000",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 },
+                 "coin_change":{
+                    "coin_identifier":{
+                       "identifier":"8bec7fd0a9fdc351adaaf07f595afefa7844eafd183625949e51dcb3b9632b890000"
+                    },
+                    "coin_action":"coin_spent"
+                 }
+              },
+              {
+                 "operation_identifier":{
+                    "index":1,
+                    "network_index":1
+                 },
+                 "type":"INPUT",
+                 "account":{
+                    "address":"atoi1qr49znuapruu3fhwcfd4vsq2y3a0l9k8zc6pv6ak70g4hd9jq8fr2lqf6et"
+                 },
+                 "amount":{
+                    "value":"-4000000",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 },
+                 "coin_change":{
+                    "coin_identifier":{
+                       "identifier":"8bec7fd0a9fdc351adaaf07f595afefa7844eafd183625949e51dcb3b9632b890100"
+                    },
+                    "coin_action":"coin_spent"
+                 }
+              },
+              {
+                 "operation_identifier":{
+                    "index":2
+                 },
+                 "type":"SIG_LOCKED_SINGLE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qpmppfmvwlg5qjkwd8084ceh0emw6y9gegpmesn2vvrlacfep834wyqsxww"
+                 },
+                 "amount":{
+                    "value":"8604736",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              },
+              {
+                 "operation_identifier":{
+                    "index":3
+                 },
+                 "type":"SIG_LOCKED_SINGLE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qp08ypmqn53kxxmj7d60wqp6hwtcc25sv8y950j7e35fjnj3dmpxyp7l5y9"
+                 },
+                 "amount":{
+                    "value":"395264",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              },
+              {
+                 "operation_identifier":{
+                    "index":4
+                 },
+                 "type":"SIG_LOCKED_DUST_ALLOWANCE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qp08ypmqn53kxxmj7d60wqp6hwtcc25sv8y950j7e35fjnj3dmpxyp7l5y9"
+                 },
+                 "amount":{
+                    "value":"1000000",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              }
+           ],
+           "metadata":{
+              "utxo_inputs_metadata":{
+                 "8bec7fd0a9fdc351adaaf07f595afefa7844eafd183625949e51dcb3b9632b890000":{
+                    "messageId":"2f2e4f2d79cae50aecf9a26292f693b1335a692d1e3e452983aeec165968dad8",
+                    "transactionId":"8bec7fd0a9fdc351adaaf07f595afefa7844eafd183625949e51dcb3b9632b89",
+                    "outputIndex":0,
+                    "isSpent":false,
+                    "ledgerIndex":1438614,
+                    "output":{
+                       "type":0,
+                       "address":{
+                          "type":0,
+                          "address":"ea514f9d08f9c8a6eec25b56400a247aff96c71634166bb6f3d15bb4b201d235"
+                       },
+                       "amount":6000000
+                    }
+                 },
+                 "8bec7fd0a9fdc351adaaf07f595afefa7844eafd183625949e51dcb3b9632b890100":{
+                    "messageId":"2f2e4f2d79cae50aecf9a26292f693b1335a692d1e3e452983aeec165968dad8",
+                    "transactionId":"8bec7fd0a9fdc351adaaf07f595afefa7844eafd183625949e51dcb3b9632b89",
+                    "outputIndex":1,
+                    "isSpent":false,
+                    "ledgerIndex":1438614,
+                    "output":{
+                       "type":0,
+                       "address":{
+                          "type":0,
+                          "address":"ea514f9d08f9c8a6eec25b56400a247aff96c71634166bb6f3d15bb4b201d235"
+                       },
+                       "amount":4000000
+                    }
+                 }
+              }
+           }
+        }
+    "#,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn one_signing_payload_per_address() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+
+    let response = payloads(payloads_request(), rosetta_config, server_state)
+        .await
+        .unwrap();
+
+    // both inputs are owned by the same address
+    assert_eq!(1, response.payloads.len());
+    assert_eq!(
+        "atoi1qr49znuapruu3fhwcfd4vsq2y3a0l9k8zc6pv6ak70g4hd9jq8fr2lqf6et",
+        response.payloads[0].account_identifier.address
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn indexation_is_attached() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+
+    let mut request = payloads_request();
+    request.metadata.indexation = Some(IndexationMetadata {
+        index: hex::encode("invoice"),
+        data: hex::encode("4711"),
+    });
+    let network_identifier = request.network_identifier.clone();
+
+    let response = payloads(request, rosetta_config.clone(), server_state.clone())
+        .await
+        .unwrap();
+
+    let parse_request = ConstructionParseRequest {
+        network_identifier,
+        signed: false,
+        transaction: response.unsigned_transaction,
+    };
+    let parse_response = parse(parse_request, rosetta_config, server_state).await.unwrap();
+
+    let indexation = parse_response.metadata.unwrap().indexation;
+    assert_eq!(hex::encode("invoice"), indexation.index);
+    assert_eq!(hex::encode("4711"), indexation.data);
+    assert_eq!(5, parse_response.operations.len());
+}
+
+#[tokio::test]
+#[serial]
+async fn invalid_indexation() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+
+    let mut request = payloads_request();
+    // the index must not be empty
+    request.metadata.indexation = Some(IndexationMetadata {
+        index: String::new(),
+        data: hex::encode("4711"),
+    });
+
+    let error = payloads(request, rosetta_config, server_state).await.unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn amount_out_of_range() {
+    // the first amount overflows u64, the second one exceeds the token supply
+    for amount in &["18446744073709551616".to_string(), u64::MAX.to_string()] {
+        let rosetta_config = default_rosetta_config();
+        let server_state = ServerState::new(&rosetta_config);
+
+        let mut request = payloads_request();
+        request.operations[2].amount.as_mut().unwrap().value = amount.clone();
+
+        let error = payloads(request, rosetta_config, server_state).await.unwrap_err();
+
+        assert_eq!(1, error.code());
+    }
+}
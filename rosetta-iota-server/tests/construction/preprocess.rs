@@ -3,7 +3,7 @@
 
 use crate::{test_request, Request};
 
-use rosetta_iota_server::construction::preprocess::ConstructionPreprocessRequest;
+use rosetta_iota_server::{construction::preprocess::ConstructionPreprocessRequest, types::InputSelectionStrategy};
 
 use serial_test::serial;
 
@@ -105,5 +105,199 @@ async fn valid_request() {
     assert_eq!(
         "8bec7fd0a9fdc351adaaf07f595afefa7844eafd183625949e51dcb3b9632b890000",
         response.options.utxo_inputs[0]
+    );
+
+    // only the output of 395264 is a dust output
+    assert_eq!(
+        vec!["atoi1qp08ypmqn53kxxmj7d60wqp6hwtcc25sv8y950j7e35fjnj3dmpxyp7l5y9".to_string()],
+        response.options.dust_addresses
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn inputs_selected_from_sender() {
+    let request: ConstructionPreprocessRequest = serde_json::from_str(
+        r#"
+        {
+           "network_identifier":{
+              "blockchain":"iota",
+              "network":"chrysalis-mainnet"
+           },
+           "operations":[
+              {
+                 "operation_identifier":{
+                    "index":0
+                 },
+                 "type":"SIG_LOCKED_SINGLE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qpmppfmvwlg5qjkwd8084ceh0emw6y9gegpmesn2vvrlacfep834wyqsxww"
+                 },
+                 "amount":{
+                    "value":"8604736",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              },
+              {
+                 "operation_identifier":{
+                    "index":1
+                 },
+                 "type":"SIG_LOCKED_DUST_ALLOWANCE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qp08ypmqn53kxxmj7d60wqp6hwtcc25sv8y950j7e35fjnj3dmpxyp7l5y9"
+                 },
+                 "amount":{
+                    "value":"1000000",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              }
+           ],
+           "metadata":{
+              "sender":{
+                 "address":"atoi1qr49znuapruu3fhwcfd4vsq2y3a0l9k8zc6pv6ak70g4hd9jq8fr2lqf6et"
+              },
+              "input_selection_strategy":"dust_consolidating"
+           }
+        }
+    "#,
     )
+    .unwrap();
+
+    let response = test_request(Request::ConstructionPreprocess(request))
+        .await
+        .unwrap_construction_preprocess_response()
+        .unwrap();
+
+    assert!(response.options.utxo_inputs.is_empty());
+
+    let input_selection = response.options.input_selection.unwrap();
+    assert_eq!(
+        "atoi1qr49znuapruu3fhwcfd4vsq2y3a0l9k8zc6pv6ak70g4hd9jq8fr2lqf6et",
+        input_selection.sender.address
+    );
+    assert_eq!(9604736, input_selection.amount);
+    assert_eq!(InputSelectionStrategy::DustConsolidating, input_selection.strategy);
+
+    // neither recipient receives a dust output
+    assert!(response.options.dust_addresses.is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn output_amount_overflow() {
+    let request: ConstructionPreprocessRequest = serde_json::from_str(
+        r#"
+        {
+           "network_identifier":{
+              "blockchain":"iota",
+              "network":"chrysalis-mainnet"
+           },
+           "operations":[
+              {
+                 "operation_identifier":{
+                    "index":0
+                 },
+                 "type":"SIG_LOCKED_SINGLE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qpmppfmvwlg5qjkwd8084ceh0emw6y9gegpmesn2vvrlacfep834wyqsxww"
+                 },
+                 "amount":{
+                    "value":"18446744073709551615",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              },
+              {
+                 "operation_identifier":{
+                    "index":1
+                 },
+                 "type":"SIG_LOCKED_SINGLE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qp08ypmqn53kxxmj7d60wqp6hwtcc25sv8y950j7e35fjnj3dmpxyp7l5y9"
+                 },
+                 "amount":{
+                    "value":"1000000",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              }
+           ],
+           "metadata":{
+              "sender":{
+                 "address":"atoi1qr49znuapruu3fhwcfd4vsq2y3a0l9k8zc6pv6ak70g4hd9jq8fr2lqf6et"
+              }
+           }
+        }
+    "#,
+    )
+    .unwrap();
+
+    let error = test_request(Request::ConstructionPreprocess(request))
+        .await
+        .unwrap_construction_preprocess_response()
+        .unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn indexation_passed_to_options() {
+    let request: ConstructionPreprocessRequest = serde_json::from_str(
+        r#"
+        {
+           "network_identifier":{
+              "blockchain":"iota",
+              "network":"chrysalis-mainnet"
+           },
+           "operations":[
+              {
+                 "operation_identifier":{
+                    "index":0
+                 },
+                 "type":"SIG_LOCKED_SINGLE_OUTPUT",
+                 "account":{
+                    "address":"atoi1qpmppfmvwlg5qjkwd8084ceh0emw6y9gegpmesn2vvrlacfep834wyqsxww"
+                 },
+                 "amount":{
+                    "value":"8604736",
+                    "currency":{
+                       "symbol":"IOTA",
+                       "decimals":0
+                    }
+                 }
+              }
+           ],
+           "metadata":{
+              "sender":{
+                 "address":"atoi1qr49znuapruu3fhwcfd4vsq2y3a0l9k8zc6pv6ak70g4hd9jq8fr2lqf6et"
+              },
+              "indexation":{
+                 "index":"526f7365747461",
+                 "data":"48656c6c6f"
+              }
+           }
+        }
+    "#,
+    )
+    .unwrap();
+
+    let response = test_request(Request::ConstructionPreprocess(request))
+        .await
+        .unwrap_construction_preprocess_response()
+        .unwrap();
+
+    let indexation = response.options.indexation.unwrap();
+    assert_eq!("526f7365747461", indexation.index);
+    assert_eq!("48656c6c6f", indexation.data);
 }
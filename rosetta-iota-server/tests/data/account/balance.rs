@@ -3,19 +3,31 @@
 
 use crate::{
     config::{
-        VALID_BECH32_ADDRESS_WITH_BALANCE, VALID_BLOCKCHAIN, VALID_NETWORK, WRONG_ADDRESS_FORMAT, WRONG_BLOCKCHAIN,
-        WRONG_NETWORK,
+        default_rosetta_config, VALID_BECH32_ADDRESS_WITH_BALANCE, VALID_BLOCKCHAIN, VALID_NETWORK,
+        WRONG_ADDRESS_FORMAT, WRONG_BLOCKCHAIN, WRONG_NETWORK,
     },
+    dummy_node::start_dummy_node,
     test_request, Request,
 };
 
 use rosetta_iota_server::{
-    data::account::balance::*,
-    types::{AccountIdentifier, NetworkIdentifier},
+    consts::TREASURY_ACCOUNT,
+    data::account::{
+        balance::*,
+        balance_store::{BalanceStore, BALANCE_DIFFS_FILE, BOOTSTRAP_BALANCES_FILE, SEP_INDEX_FILE},
+    },
+    error::ApiError,
+    follower::sync_balances,
+    state::ServerState,
+    types::{AccountIdentifier, NetworkIdentifier, PartialBlockIdentifier},
+    RosettaConfig,
 };
 
+use bee_message::address::Address;
 use serial_test::serial;
 
+use std::{collections::HashMap, fs};
+
 #[tokio::test]
 #[serial]
 async fn valid_request() {
@@ -27,6 +39,7 @@ async fn valid_request() {
         account_identifier: AccountIdentifier {
             address: VALID_BECH32_ADDRESS_WITH_BALANCE.to_string(),
         },
+        block_identifier: None,
         currencies: None,
     };
 
@@ -36,13 +49,168 @@ async fn valid_request() {
         .unwrap();
 
     assert_eq!(1438441, response.block_identifier.index);
-    assert_eq!("1438441", response.block_identifier.hash);
+    assert_eq!(
+        "feefb290f9d0d467f81523483a82d80827d4e0eb8f2c0514c1805185ca0690aa",
+        response.block_identifier.hash
+    );
     assert_eq!(1, response.balances.len());
     assert_eq!("IOTA", response.balances[0].currency.symbol);
     assert_eq!(0, response.balances[0].currency.decimals);
     assert_eq!("20651169480", response.balances[0].value);
 }
 
+#[tokio::test]
+#[serial]
+async fn treasury_balance() {
+    let request = AccountBalanceRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        account_identifier: AccountIdentifier {
+            address: TREASURY_ACCOUNT.to_string(),
+        },
+        block_identifier: None,
+        currencies: None,
+    };
+
+    let response = test_request(Request::AccountBalance(request))
+        .await
+        .unwrap_account_balance_response()
+        .unwrap();
+
+    assert_eq!(1438448, response.block_identifier.index);
+    assert_eq!("145115236385079", response.balances[0].value);
+}
+
+fn rosetta_config_with_snapshot(sep_index: u32) -> RosettaConfig {
+    let snapshot_dir = std::env::temp_dir().join("rosetta-iota-test-snapshot");
+    fs::create_dir_all(&snapshot_dir).unwrap();
+    let _ = fs::remove_file(snapshot_dir.join(BALANCE_DIFFS_FILE));
+    fs::write(snapshot_dir.join(SEP_INDEX_FILE), sep_index.to_string()).unwrap();
+    fs::write(
+        snapshot_dir.join(BOOTSTRAP_BALANCES_FILE),
+        format!(
+            r#"[{{"account_identifier":{{"address":"{}"}},"currency":{{"symbol":"IOTA","decimals":0}},"value":"20000000000"}}]"#,
+            VALID_BECH32_ADDRESS_WITH_BALANCE
+        ),
+    )
+    .unwrap();
+
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.snapshot_dir = Some(snapshot_dir);
+    rosetta_config
+}
+
+async fn historical_balance(
+    rosetta_config: &RosettaConfig,
+    server_state: &ServerState,
+    milestone_index: u32,
+) -> String {
+    let request = AccountBalanceRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        account_identifier: AccountIdentifier {
+            address: VALID_BECH32_ADDRESS_WITH_BALANCE.to_string(),
+        },
+        block_identifier: Some(PartialBlockIdentifier {
+            index: Some(milestone_index),
+            hash: None,
+        }),
+        currencies: None,
+    };
+
+    let response = account_balance(request, rosetta_config.clone(), server_state.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(milestone_index, response.block_identifier.index);
+    response.balances[0].value.clone()
+}
+
+#[tokio::test]
+#[serial]
+async fn historical_balances() {
+    let rosetta_config = rosetta_config_with_snapshot(1438447);
+    let server_state = ServerState::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    sync_balances(&server_state).await.unwrap();
+    // milestone 1438448 consumes 10000000000 and creates 20651169480 for the address
+    let balances = (
+        historical_balance(&rosetta_config, &server_state, 1438447).await,
+        historical_balance(&rosetta_config, &server_state, 1438448).await,
+    );
+    dummy_node.shutdown().await;
+
+    assert_eq!(("20000000000".to_string(), "30651169480".to_string()), balances);
+}
+
+#[tokio::test]
+#[serial]
+async fn historical_balances_survive_restart() {
+    let rosetta_config = rosetta_config_with_snapshot(1438447);
+
+    let dummy_node = start_dummy_node().await;
+    sync_balances(&ServerState::new(&rosetta_config)).await.unwrap();
+    // the restarted server replays the applied balance diffs instead of fetching them again
+    let server_state = ServerState::new(&rosetta_config);
+    let balance = historical_balance(&rosetta_config, &server_state, 1438448).await;
+    dummy_node.shutdown().await;
+
+    assert_eq!(1438448, server_state.balance_store.unwrap().ledger_index());
+    assert_eq!("30651169480", balance);
+}
+
+#[test]
+#[serial]
+fn genesis_follows_snapshot() {
+    let rosetta_config = rosetta_config_with_snapshot(1438447);
+
+    // the bootstrap balances already include the milestone at the sep_index
+    assert_eq!(1438448, ServerState::new(&rosetta_config).genesis_index);
+}
+
+#[tokio::test]
+#[serial]
+async fn snapshot_before_pruning_index() {
+    // the dummy node pruned the milestones up to 1438000
+    let rosetta_config = rosetta_config_with_snapshot(1437999);
+    let server_state = ServerState::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    let error = sync_balances(&server_state).await.unwrap_err();
+    dummy_node.shutdown().await;
+
+    assert!(matches!(error, ApiError::NonRetriable(message) if message.contains("pruned milestone 1438000")));
+}
+
+#[test]
+fn negative_balance_is_not_stored() {
+    let address = Address::try_from_bech32(VALID_BECH32_ADDRESS_WITH_BALANCE).unwrap();
+    let balance_store = BalanceStore::new(1438447, vec![(address, 5)].into_iter().collect());
+
+    let mut balance_diffs = HashMap::new();
+    balance_diffs.insert(address, -10);
+    let error = balance_store.apply_milestone(1438448, balance_diffs).unwrap_err();
+
+    assert_eq!(1, error.code());
+    assert_eq!(1438447, balance_store.ledger_index());
+    assert_eq!(5, balance_store.balance(&address, 1438447).unwrap());
+}
+
+#[tokio::test]
+#[should_panic]
+#[serial]
+async fn historical_balance_before_snapshot() {
+    let rosetta_config = rosetta_config_with_snapshot(1438447);
+    let server_state = ServerState::new(&rosetta_config);
+
+    historical_balance(&rosetta_config, &server_state, 1438446).await;
+}
+
 #[tokio::test]
 #[should_panic]
 #[serial]
@@ -55,6 +223,7 @@ async fn wrong_blockchain() {
         account_identifier: AccountIdentifier {
             address: VALID_BECH32_ADDRESS_WITH_BALANCE.to_string(),
         },
+        block_identifier: None,
         currencies: None,
     };
 
@@ -76,6 +245,7 @@ async fn wrong_network() {
         account_identifier: AccountIdentifier {
             address: VALID_BECH32_ADDRESS_WITH_BALANCE.to_string(),
         },
+        block_identifier: None,
         currencies: None,
     };
 
@@ -97,6 +267,7 @@ async fn wrong_address_format() {
         account_identifier: AccountIdentifier {
             address: WRONG_ADDRESS_FORMAT.to_string(),
         },
+        block_identifier: None,
         currencies: None,
     };
 
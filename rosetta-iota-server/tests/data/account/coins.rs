@@ -3,15 +3,15 @@
 
 use crate::{
     config::{
-        VALID_BECH32_ADDRESS_WITH_BALANCE, VALID_BLOCKCHAIN, VALID_NETWORK, WRONG_ADDRESS_FORMAT, WRONG_BLOCKCHAIN,
-        WRONG_NETWORK,
+        PENDING_SPEND_TRANSACTION_ID, VALID_BECH32_ADDRESS_WITH_BALANCE, VALID_BLOCKCHAIN, VALID_NETWORK,
+        WRONG_ADDRESS_FORMAT, WRONG_BLOCKCHAIN, WRONG_NETWORK,
     },
     test_request, Request,
 };
 
 use rosetta_iota_server::{
     data::account::coins::*,
-    types::{AccountIdentifier, NetworkIdentifier},
+    types::{AccountIdentifier, MempoolState, NetworkIdentifier},
 };
 
 use serial_test::serial;
@@ -36,7 +36,10 @@ async fn valid_request() {
         .unwrap();
 
     assert_eq!(1438495, response.block_identifier.index);
-    assert_eq!("1438495", response.block_identifier.hash);
+    assert_eq!(
+        "0a3060a16bb7a3671e446f3cbb524940cc377d4b0ccd934911cc378dc263cfd1",
+        response.block_identifier.hash
+    );
     assert_eq!(1, response.coins.len());
     assert_eq!("20651169480", response.coins[0].amount.value);
     assert_eq!(
@@ -45,6 +48,56 @@ async fn valid_request() {
     );
 }
 
+#[tokio::test]
+#[serial]
+async fn valid_request_including_mempool() {
+    let request = AccountCoinsRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        account_identifier: AccountIdentifier {
+            address: VALID_BECH32_ADDRESS_WITH_BALANCE.to_string(),
+        },
+        include_mempool: true,
+    };
+
+    let response = test_request(Request::AccountCoins(request))
+        .await
+        .unwrap_account_coins_response()
+        .unwrap();
+
+    // the confirmed coin is spent by a pending transaction, which sends most of it back to the address
+    assert_eq!(1, response.coins.len());
+    assert_eq!(
+        format!("{}0100", PENDING_SPEND_TRANSACTION_ID),
+        response.coins[0].coin_identifier.identifier
+    );
+    assert_eq!("20650169480", response.coins[0].amount.value);
+    assert_eq!(
+        Some(MempoolState::PendingCreation),
+        response.coins[0]
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.mempool_state.clone())
+    );
+
+    let pending_spent_coins = response.metadata.unwrap().pending_spent_coins;
+    assert_eq!(1, pending_spent_coins.len());
+    assert_eq!(
+        "d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000",
+        pending_spent_coins[0].coin_identifier.identifier
+    );
+    assert_eq!("20651169480", pending_spent_coins[0].amount.value);
+    assert_eq!(
+        Some(MempoolState::PendingSpend),
+        pending_spent_coins[0]
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.mempool_state.clone())
+    );
+}
+
 #[tokio::test]
 #[should_panic]
 #[serial]
@@ -0,0 +1,96 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{default_rosetta_config, VALID_BLOCKCHAIN, VALID_NETWORK},
+    dummy_node::start_dummy_node,
+};
+
+use rosetta_iota_server::{
+    data::block::{block_cache::BlockCache, block_handler::*},
+    state::ServerState,
+    types::{NetworkIdentifier, PartialBlockIdentifier},
+    RosettaConfig,
+};
+
+use serial_test::serial;
+
+use std::{fs, path::PathBuf};
+
+const MILESTONE_HASH: &str = "8abc7c6b1b871a2bf6c5112d9bd0d7f310176fbe35127be269006bc1becc94e4";
+
+fn block_request(index: Option<u32>, hash: Option<&str>) -> BlockRequest {
+    BlockRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        block_identifier: PartialBlockIdentifier {
+            index,
+            hash: hash.map(|hash| hash.to_string()),
+        },
+    }
+}
+
+fn empty_block_cache_dir() -> PathBuf {
+    let block_cache_dir = std::env::temp_dir().join("rosetta-iota-test-block-cache");
+    let _ = fs::remove_dir_all(&block_cache_dir);
+    block_cache_dir
+}
+
+fn rosetta_config_with_block_cache() -> RosettaConfig {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.block_cache_dir = Some(empty_block_cache_dir());
+    rosetta_config
+}
+
+#[tokio::test]
+#[serial]
+async fn cached_block_outlives_node() {
+    let rosetta_config = rosetta_config_with_block_cache();
+
+    let dummy_node = start_dummy_node().await;
+    let built = block(
+        block_request(Some(1438448), None),
+        rosetta_config.clone(),
+        ServerState::new(&rosetta_config),
+    )
+    .await
+    .unwrap();
+    dummy_node.shutdown().await;
+
+    // the node is gone and the state is new, so the block can only come from disk
+    let cached = block(
+        block_request(Some(1438448), Some(MILESTONE_HASH)),
+        rosetta_config.clone(),
+        ServerState::new(&rosetta_config),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(built.block.block_identifier.hash, cached.block.block_identifier.hash);
+    assert_eq!(built.block.timestamp, cached.block.timestamp);
+    assert_eq!(built.block.transactions.len(), cached.block.transactions.len());
+}
+
+#[tokio::test]
+#[serial]
+async fn oldest_index_follows_contiguous_blocks() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+    let block_cache = BlockCache::open(&empty_block_cache_dir()).unwrap();
+
+    let dummy_node = start_dummy_node().await;
+    let block_response = block(block_request(Some(1438448), None), rosetta_config, server_state)
+        .await
+        .unwrap();
+    dummy_node.shutdown().await;
+
+    // the content does not matter, only which milestone indexes are cached
+    for milestone_index in &[1437990, 1437998, 1437999, 1438000] {
+        block_cache.insert(*milestone_index, &block_response).unwrap();
+    }
+
+    assert_eq!(1437998, block_cache.oldest_index(1438001));
+    assert_eq!(1438005, block_cache.oldest_index(1438005));
+}
@@ -1,10 +1,275 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{
+    config::{
+        default_rosetta_config, INDEXATION_MESSAGE_ID, INDEXATION_MILESTONE_INDEX, INDEXATION_TRANSACTION_ID,
+        MISSING_INPUT_TRANSACTION_ID, MULTI_TRANSACTION_MILESTONE_INDEX, VALID_BLOCKCHAIN, VALID_NETWORK,
+    },
+    dummy_node::{start_dummy_node, take_info_requests, take_requests},
+    test_request, Request,
+};
+
+use rosetta_iota_server::{
+    data::block::block_handler::*,
+    error::ApiError,
+    state::ServerState,
+    types::{NetworkIdentifier, PartialBlockIdentifier},
+};
+
 use serial_test::serial;
 
+use std::collections::HashSet;
+
+const MILESTONE_HASH: &str = "8abc7c6b1b871a2bf6c5112d9bd0d7f310176fbe35127be269006bc1becc94e4";
+const PARENT_MILESTONE_HASH: &str = "0730a63753e494d8d90adb0ca15c8b0192a748da84df48b3099ef1338558f3e6";
+
+fn block_request(index: Option<u32>, hash: Option<&str>) -> BlockRequest {
+    BlockRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        block_identifier: PartialBlockIdentifier {
+            index,
+            hash: hash.map(|hash| hash.to_string()),
+        },
+    }
+}
+
 #[tokio::test]
 #[serial]
 async fn valid_request() {
-    // TODO
+    let response = test_request(Request::Block(block_request(Some(1438448), None)))
+        .await
+        .unwrap_block_response()
+        .unwrap();
+
+    assert_eq!(1438448, response.block.block_identifier.index);
+    assert_eq!(MILESTONE_HASH, response.block.block_identifier.hash);
+    assert_eq!(1438447, response.block.parent_block_identifier.index);
+    assert_eq!(PARENT_MILESTONE_HASH, response.block.parent_block_identifier.hash);
+    assert_eq!(1634052071000, response.block.timestamp);
+    // the milestone also references a conflicting transaction
+    assert_eq!(2, response.block.transactions.len());
+
+    let metadata = response.block.transactions[0].metadata.clone().unwrap();
+    assert_eq!(
+        Some("1c7a3c3f262dc1bb75adf4709d73cb73d6de7c3616ed6fa6ae44efbb38ea522b".to_string()),
+        metadata.message_id
+    );
+    assert_eq!(4, metadata.parents.unwrap().len());
+    assert!(metadata.indexation.is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn indexation_in_metadata() {
+    let response = test_request(Request::Block(block_request(Some(INDEXATION_MILESTONE_INDEX), None)))
+        .await
+        .unwrap_block_response()
+        .unwrap();
+
+    assert_eq!(1, response.block.transactions.len());
+    let transaction = &response.block.transactions[0];
+    assert_eq!(INDEXATION_TRANSACTION_ID, transaction.transaction_identifier.hash);
+
+    let metadata = transaction.metadata.clone().unwrap();
+    assert_eq!(Some(INDEXATION_MESSAGE_ID.to_string()), metadata.message_id);
+    let indexation = metadata.indexation.unwrap();
+    // "rosetta" and "memo"
+    assert_eq!("726f7365747461", indexation.index);
+    assert_eq!("6d656d6f", indexation.data);
+}
+
+#[tokio::test]
+#[serial]
+async fn transactions_are_ordered_by_hash() {
+    let response = test_request(Request::Block(block_request(
+        Some(MULTI_TRANSACTION_MILESTONE_INDEX),
+        None,
+    )))
+    .await
+    .unwrap_block_response()
+    .unwrap();
+
+    let hashes = response
+        .block
+        .transactions
+        .iter()
+        .map(|transaction| transaction.transaction_identifier.hash.as_str())
+        .collect::<Vec<_>>();
+    // the node lists the created outputs in another order; conflicting transactions follow the included ones
+    assert_eq!(
+        vec![
+            "95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e7",
+            INDEXATION_TRANSACTION_ID,
+            MISSING_INPUT_TRANSACTION_ID
+        ],
+        hashes
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn outputs_and_messages_are_fetched_once() {
+    take_requests();
+    test_request(Request::Block(block_request(
+        Some(MULTI_TRANSACTION_MILESTONE_INDEX),
+        None,
+    )))
+    .await
+    .unwrap_block_response()
+    .unwrap();
+    let requests = take_requests();
+
+    // both transactions spend the same output
+    assert_eq!(
+        1,
+        requests
+            .iter()
+            .filter(|request| *request == "outputs/dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f0d00")
+            .count()
+    );
+    assert_eq!(
+        requests.len(),
+        requests.iter().collect::<HashSet<_>>().len(),
+        "{:?}",
+        requests
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn node_info_is_fetched_once() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    // connecting to the node checks its health first
+    server_state.client_pool.get().await.unwrap();
+    take_info_requests();
+    let response = block(block_request(Some(1438448), None), rosetta_config, server_state).await;
+    let info_requests = take_info_requests();
+    dummy_node.shutdown().await;
+
+    response.unwrap();
+    // only the pruning index is looked up
+    assert_eq!(1, info_requests);
+}
+
+#[tokio::test]
+#[serial]
+async fn missing_inputs_of_conflicting_transactions_are_skipped() {
+    let response = test_request(Request::Block(block_request(
+        Some(MULTI_TRANSACTION_MILESTONE_INDEX),
+        None,
+    )))
+    .await
+    .unwrap_block_response()
+    .unwrap();
+
+    let conflicting = response
+        .block
+        .transactions
+        .iter()
+        .find(|transaction| transaction.transaction_identifier.hash == MISSING_INPUT_TRANSACTION_ID)
+        .unwrap();
+    // the node does not know the input, only the output is left
+    assert_eq!(1, conflicting.operations.len());
+    assert_eq!("SIG_LOCKED_SINGLE_OUTPUT", conflicting.operations[0].type_);
+    assert!(conflicting.metadata.clone().unwrap().conflict_reason.is_some());
+}
+
+#[tokio::test]
+#[serial]
+async fn known_hash_resolves_to_index() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    block(
+        block_request(Some(1438448), None),
+        rosetta_config.clone(),
+        server_state.clone(),
+    )
+    .await
+    .unwrap();
+    let response = block(block_request(None, Some(MILESTONE_HASH)), rosetta_config, server_state).await;
+    dummy_node.shutdown().await;
+
+    assert_eq!(1438448, response.unwrap().block.block_identifier.index);
+}
+
+#[tokio::test]
+#[should_panic]
+#[serial]
+async fn index_does_not_relate_to_hash() {
+    test_request(Request::Block(block_request(
+        Some(1438448),
+        Some(PARENT_MILESTONE_HASH),
+    )))
+    .await
+    .unwrap_block_response()
+    .unwrap();
+}
+
+async fn block_with_genesis(genesis_index: u32, milestone_index: u32) -> Result<BlockResponse, ApiError> {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.genesis_index = Some(genesis_index);
+    let server_state = ServerState::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    let response = block(block_request(Some(milestone_index), None), rosetta_config, server_state).await;
+    dummy_node.shutdown().await;
+    response
+}
+
+#[tokio::test]
+#[serial]
+async fn genesis_block_is_its_own_parent() {
+    let response = block_with_genesis(1438448, 1438448).await.unwrap();
+
+    assert_eq!(MILESTONE_HASH, response.block.parent_block_identifier.hash);
+    assert_eq!(1438448, response.block.parent_block_identifier.index);
+}
+
+#[tokio::test]
+#[serial]
+async fn block_before_genesis() {
+    let response = block_with_genesis(1438448, 1438447).await;
+
+    assert_eq!(10, response.unwrap_err().code());
+}
+
+#[tokio::test]
+#[serial]
+async fn pruned_block() {
+    // the dummy node pruned everything up to milestone 1438000 and can not resolve older milestones
+    let error = test_request(Request::Block(block_request(Some(1437000), None)))
+        .await
+        .unwrap_block_response()
+        .unwrap_err();
+
+    assert_eq!(7, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn pruned_parent_is_not_made_up() {
+    let error = test_request(Request::Block(block_request(Some(1438001), None)))
+        .await
+        .unwrap_block_response()
+        .unwrap_err();
+
+    assert_eq!(7, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn pruned_block_before_genesis() {
+    // the milestone is rejected before the node is asked for it
+    let response = block_with_genesis(1438448, 1437000).await;
+
+    assert_eq!(10, response.unwrap_err().code());
 }
@@ -0,0 +1,156 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{
+        default_rosetta_config, CONFLICTING_MESSAGE_ID, CONFLICTING_TRANSACTION_ID, VALID_BLOCKCHAIN, VALID_NETWORK,
+        WRONG_NETWORK,
+    },
+    dummy_node::start_dummy_node,
+    test_request, Request,
+};
+
+use rosetta_iota_server::{
+    data::block::block_transaction::*,
+    operations::SKIPPED,
+    state::ServerState,
+    types::{BlockIdentifier, NetworkIdentifier, TransactionIdentifier},
+};
+
+use serial_test::serial;
+
+const MILESTONE_INDEX: u32 = 1438448;
+const MILESTONE_HASH: &str = "8abc7c6b1b871a2bf6c5112d9bd0d7f310176fbe35127be269006bc1becc94e4";
+// the transaction that created the output of milestone 1438448; the dummy node serves its message with the payload of
+// transaction TRANSACTION_OF_MESSAGE
+const TRANSACTION_IN_MILESTONE: &str = "d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b5019";
+const TRANSACTION_OF_MESSAGE: &str = "95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e7";
+const TRANSACTION_NOT_IN_MILESTONE: &str = "dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f";
+
+fn block_transaction_request(network: &str, transaction_hash: &str) -> BlockTransactionRequest {
+    BlockTransactionRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: network.to_string(),
+        },
+        block_identifier: BlockIdentifier {
+            index: MILESTONE_INDEX,
+            hash: MILESTONE_HASH.to_string(),
+        },
+        transaction_identifier: TransactionIdentifier {
+            hash: transaction_hash.to_string(),
+        },
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn valid_request() {
+    let request = block_transaction_request(VALID_NETWORK, TRANSACTION_IN_MILESTONE);
+
+    let response = test_request(Request::BlockTransaction(request))
+        .await
+        .unwrap_block_transaction_response()
+        .unwrap();
+
+    assert_eq!(
+        TRANSACTION_IN_MILESTONE,
+        response.transaction.transaction_identifier.hash
+    );
+    // one input and 16 outputs
+    assert_eq!(17, response.transaction.operations.len());
+    assert_eq!("INPUT", response.transaction.operations[0].type_);
+    assert!(response
+        .transaction
+        .operations
+        .iter()
+        .all(|operation| operation.status.as_deref() != Some(SKIPPED) && operation.coin_change.is_some()));
+    let metadata = response.transaction.metadata.unwrap();
+    assert_eq!(
+        Some("1c7a3c3f262dc1bb75adf4709d73cb73d6de7c3616ed6fa6ae44efbb38ea522b".to_string()),
+        metadata.message_id
+    );
+    assert!(metadata.conflict_reason.is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn conflicting_transaction() {
+    let request = block_transaction_request(VALID_NETWORK, CONFLICTING_TRANSACTION_ID);
+
+    let response = test_request(Request::BlockTransaction(request))
+        .await
+        .unwrap_block_transaction_response()
+        .unwrap();
+
+    assert_eq!(
+        CONFLICTING_TRANSACTION_ID,
+        response.transaction.transaction_identifier.hash
+    );
+    assert_eq!(16, response.transaction.operations.len());
+    assert!(response
+        .transaction
+        .operations
+        .iter()
+        .all(|operation| operation.status.as_deref() == Some(SKIPPED) && operation.coin_change.is_none()));
+    let metadata = response.transaction.metadata.unwrap();
+    assert_eq!(Some("input UTXO already spent".to_string()), metadata.conflict_reason);
+    assert_eq!(Some(CONFLICTING_MESSAGE_ID.to_string()), metadata.message_id);
+}
+
+#[tokio::test]
+#[serial]
+async fn transaction_not_in_block() {
+    let request = block_transaction_request(VALID_NETWORK, TRANSACTION_NOT_IN_MILESTONE);
+
+    let error = test_request(Request::BlockTransaction(request))
+        .await
+        .unwrap_block_transaction_response()
+        .unwrap_err();
+
+    assert_eq!(1, error.code());
+    assert!(!error.retriable());
+}
+
+#[tokio::test]
+#[serial]
+async fn pruned_block() {
+    let mut request = block_transaction_request(VALID_NETWORK, TRANSACTION_IN_MILESTONE);
+    request.block_identifier.index = 1437000;
+
+    let error = test_request(Request::BlockTransaction(request))
+        .await
+        .unwrap_block_transaction_response()
+        .unwrap_err();
+
+    assert_eq!(7, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn pruned_block_before_genesis() {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.genesis_index = Some(1438448);
+    let server_state = ServerState::new(&rosetta_config);
+    let mut request = block_transaction_request(VALID_NETWORK, TRANSACTION_IN_MILESTONE);
+    request.block_identifier.index = 1437000;
+
+    let dummy_node = start_dummy_node().await;
+    let response = block_transaction(request, rosetta_config, server_state).await;
+    dummy_node.shutdown().await;
+
+    assert_eq!(10, response.unwrap_err().code());
+}
+
+#[tokio::test]
+#[serial]
+async fn wrong_network() {
+    let request = block_transaction_request(WRONG_NETWORK, TRANSACTION_NOT_IN_MILESTONE);
+
+    let error = test_request(Request::BlockTransaction(request))
+        .await
+        .unwrap_block_transaction_response()
+        .unwrap_err();
+
+    assert_eq!(3, error.code());
+}
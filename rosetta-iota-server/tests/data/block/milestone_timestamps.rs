@@ -0,0 +1,19 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use rosetta_iota_server::data::block::milestone_timestamps::MilestoneTimestamps;
+
+#[test]
+fn narrow_to_cached_milestones() {
+    let milestone_timestamps = MilestoneTimestamps::new();
+    milestone_timestamps.insert(10, 100);
+    milestone_timestamps.insert(20, 200);
+    milestone_timestamps.insert(30, 300);
+
+    assert_eq!((20, 30), milestone_timestamps.narrow(250, 1, 100));
+    assert_eq!((20, 30), milestone_timestamps.narrow(200, 1, 100));
+    assert_eq!((1, 10), milestone_timestamps.narrow(50, 1, 100));
+    assert_eq!((30, 100), milestone_timestamps.narrow(350, 1, 100));
+    // cached milestones outside of the range are ignored
+    assert_eq!((15, 25), milestone_timestamps.narrow(250, 15, 25));
+}
@@ -1,4 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod block_cache;
 mod block_handler;
+mod block_transaction;
+mod milestone_timestamps;
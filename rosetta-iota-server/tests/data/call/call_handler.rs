@@ -0,0 +1,203 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{default_rosetta_config, VALID_BECH32_ADDRESS_WITH_BALANCE, VALID_BLOCKCHAIN, VALID_NETWORK},
+    dummy_node::{start_dummy_node, MILESTONES},
+    test_request, Request,
+};
+
+use rosetta_iota_server::{
+    data::call::call_handler::*, error::ApiError, follower::sync_milestones, state::ServerState,
+    types::NetworkIdentifier,
+};
+
+use serde_json::{json, Map, Value};
+use serial_test::serial;
+
+fn call_request(method: &str, parameters: Value) -> CallRequest {
+    let parameters: Map<String, Value> = serde_json::from_value(parameters).unwrap();
+    CallRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        method: method.to_string(),
+        parameters,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn get_milestone() {
+    let request = call_request(GET_MILESTONE, json!({ "index": 1438448 }));
+
+    let response = test_request(Request::Call(request))
+        .await
+        .unwrap_call_response()
+        .unwrap();
+
+    let result: GetMilestoneResult = serde_json::from_value(response.result).unwrap();
+    assert!(response.idempotent);
+    assert_eq!(1438448, result.index);
+    assert_eq!(MILESTONES[3].1, result.message_id);
+    assert_eq!(MILESTONES[3].2, result.timestamp);
+}
+
+#[tokio::test]
+#[serial]
+async fn get_spent_output() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    sync_milestones(&server_state, &rosetta_config).await.unwrap();
+    let request = call_request(
+        GET_OUTPUT,
+        json!({ "output_id": "dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f0d00" }),
+    );
+    let response = call(request, rosetta_config, server_state).await.unwrap();
+    dummy_node.shutdown().await;
+
+    let result: GetOutputResult = serde_json::from_value(response.result).unwrap();
+    assert!(!response.idempotent);
+    assert!(result.output.is_spent);
+    assert_eq!(1438448, result.spent_in_block.unwrap().index);
+    // the transaction of the message that created the outputs of milestone 1438448
+    assert_eq!(
+        "95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e7",
+        result.spent_by_transaction.unwrap().hash
+    );
+    let indexed_blocks = result.indexed_blocks.unwrap();
+    // the first block the node did not prune has an unknown parent
+    assert_eq!(1438002, indexed_blocks.oldest_index);
+    assert_eq!(1438448, indexed_blocks.latest_index);
+}
+
+#[tokio::test]
+#[serial]
+async fn get_spent_output_without_indexed_blocks() {
+    let request = call_request(
+        GET_OUTPUT,
+        json!({ "output_id": "dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f0d00" }),
+    );
+
+    let response = test_request(Request::Call(request))
+        .await
+        .unwrap_call_response()
+        .unwrap();
+
+    let result: GetOutputResult = serde_json::from_value(response.result).unwrap();
+    assert!(result.output.is_spent);
+    assert!(result.spent_in_block.is_none());
+    assert!(result.spent_by_transaction.is_none());
+    assert!(result.indexed_blocks.is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn dust_allowed() {
+    let request = call_request(DUST_ALLOWED, json!({ "address": VALID_BECH32_ADDRESS_WITH_BALANCE }));
+
+    let response = test_request(Request::Call(request))
+        .await
+        .unwrap_call_response()
+        .unwrap();
+
+    let result: DustAllowedResult = serde_json::from_value(response.result).unwrap();
+    assert!(!result.dust_allowed);
+    assert_eq!(1438441, result.ledger_index);
+}
+
+#[tokio::test]
+#[serial]
+async fn invalid_parameters() {
+    let request = call_request(NODE_INFO, json!({ "verbose": true }));
+
+    let error = test_request(Request::Call(request))
+        .await
+        .unwrap_call_response()
+        .unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+#[tokio::test]
+#[serial]
+async fn unsupported_method() {
+    let request = call_request("get_peers", json!({}));
+
+    let error = test_request(Request::Call(request))
+        .await
+        .unwrap_call_response()
+        .unwrap_err();
+
+    assert_eq!(1, error.code());
+}
+
+async fn find_block_by_timestamp(server_state: ServerState, timestamp: u64) -> Result<CallResponse, ApiError> {
+    let dummy_node = start_dummy_node().await;
+    let request = call_request(FIND_BLOCK_BY_TIMESTAMP, json!({ "timestamp": timestamp }));
+    let response = call(request, default_rosetta_config(), server_state).await;
+    dummy_node.shutdown().await;
+    response
+}
+
+#[tokio::test]
+#[serial]
+async fn find_block_between_milestones() {
+    let server_state = ServerState::new(&default_rosetta_config());
+
+    // five seconds after milestone 1438447
+    let response = find_block_by_timestamp(server_state.clone(), (MILESTONES[2].2 + 5) * 1000)
+        .await
+        .unwrap();
+
+    let result: FindBlockByTimestampResult = serde_json::from_value(response.result).unwrap();
+    assert_eq!(1438447, result.block_identifier.index);
+    assert_eq!(MILESTONES[2].1, result.block_identifier.hash);
+    assert_eq!(MILESTONES[2].2 * 1000, result.timestamp);
+
+    // the visited milestones are remembered
+    assert_eq!(Some(MILESTONES[2].2), server_state.milestone_timestamps.get(1438447));
+    assert_eq!(Some(MILESTONES[3].2), server_state.milestone_timestamps.get(1438448));
+}
+
+#[tokio::test]
+#[serial]
+async fn find_block_at_milestone_timestamp() {
+    let server_state = ServerState::new(&default_rosetta_config());
+
+    let response = find_block_by_timestamp(server_state, MILESTONES[1].2 * 1000)
+        .await
+        .unwrap();
+
+    let result: FindBlockByTimestampResult = serde_json::from_value(response.result).unwrap();
+    assert_eq!(1438441, result.block_identifier.index);
+    assert_eq!(MILESTONES[1].1, result.block_identifier.hash);
+}
+
+#[tokio::test]
+#[serial]
+async fn find_block_after_confirmed_milestone() {
+    let server_state = ServerState::new(&default_rosetta_config());
+
+    let response = find_block_by_timestamp(server_state, (MILESTONES[4].2 + 3600) * 1000)
+        .await
+        .unwrap();
+
+    let result: FindBlockByTimestampResult = serde_json::from_value(response.result).unwrap();
+    assert_eq!(1438448, result.block_identifier.index);
+}
+
+#[tokio::test]
+#[serial]
+async fn find_block_before_pruning_index() {
+    let server_state = ServerState::new(&default_rosetta_config());
+
+    let error = find_block_by_timestamp(server_state, (MILESTONES[0].2 - 1) * 1000)
+        .await
+        .unwrap_err();
+
+    assert_eq!(7, error.code());
+}
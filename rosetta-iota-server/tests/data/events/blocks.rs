@@ -0,0 +1,102 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{default_rosetta_config, VALID_BLOCKCHAIN, VALID_NETWORK, WRONG_NETWORK},
+    dummy_node::start_dummy_node,
+    test_request, Request,
+};
+
+use rosetta_iota_server::{
+    data::events::blocks::*,
+    follower::sync_milestones,
+    state::ServerState,
+    types::{BlockEventType, NetworkIdentifier},
+};
+
+use serial_test::serial;
+
+fn events_request(offset: Option<u64>) -> EventsBlocksRequest {
+    EventsBlocksRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        offset,
+        limit: None,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn lists_followed_milestones() {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.genesis_index = Some(1438441);
+    let server_state = ServerState::new(&rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    sync_milestones(&server_state, &rosetta_config).await.unwrap();
+    // the confirmed milestone did not change, so nothing new is recorded
+    sync_milestones(&server_state, &rosetta_config).await.unwrap();
+    dummy_node.shutdown().await;
+
+    let response = events_blocks(events_request(None), rosetta_config, server_state)
+        .await
+        .unwrap();
+
+    // the log starts at the genesis milestone, the node did not prune it yet
+    assert_eq!(7, response.max_sequence);
+    assert_eq!(8, response.events.len());
+    for (sequence, event) in response.events.iter().enumerate() {
+        assert_eq!(sequence as u64, event.sequence);
+        assert_eq!(1438441 + sequence as u32, event.block_identifier.index);
+        assert_eq!(BlockEventType::BlockAdded, event.type_);
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn sequence_survives_restart() {
+    let rosetta_config = default_rosetta_config();
+
+    let mut responses = Vec::new();
+    let dummy_node = start_dummy_node().await;
+    // every server state starts with an empty log, like a restarted server
+    for _ in 0..2 {
+        let server_state = ServerState::new(&rosetta_config);
+        sync_milestones(&server_state, &rosetta_config).await.unwrap();
+        responses.push(
+            events_blocks(events_request(Some(1438447)), rosetta_config.clone(), server_state)
+                .await
+                .unwrap(),
+        );
+    }
+    dummy_node.shutdown().await;
+
+    for response in responses {
+        // the log starts at the oldest milestone the node did not prune; sequences count from the genesis milestone 1
+        assert_eq!(1438447, response.max_sequence);
+        assert_eq!(1, response.events.len());
+        assert_eq!(1438447, response.events[0].sequence);
+        assert_eq!(1438448, response.events[0].block_identifier.index);
+    }
+}
+
+#[tokio::test]
+#[should_panic]
+#[serial]
+async fn wrong_network() {
+    let request = EventsBlocksRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: WRONG_NETWORK.to_string(),
+        },
+        offset: None,
+        limit: None,
+    };
+
+    test_request(Request::EventsBlocks(request))
+        .await
+        .unwrap_events_blocks_response()
+        .unwrap();
+}
@@ -0,0 +1,78 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{default_rosetta_config, VALID_BLOCKCHAIN, VALID_NETWORK, WRONG_NETWORK},
+    dummy_node::start_dummy_node,
+    test_request, Request,
+};
+
+use rosetta_iota_server::{data::mempool::mempool_handler::*, state::ServerState, types::NetworkIdentifier};
+
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn valid_request() {
+    let request = MempoolRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+    };
+
+    let response = test_request(Request::Mempool(request))
+        .await
+        .unwrap_mempool_response()
+        .unwrap();
+
+    // only the tips are unreferenced, their parents are already confirmed
+    assert_eq!(2, response.transaction_identifiers.len());
+}
+
+#[tokio::test]
+#[serial]
+async fn snapshot_is_reused() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
+    let request = MempoolRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+    };
+
+    let dummy_node = start_dummy_node().await;
+    let response = mempool(request.clone(), rosetta_config.clone(), server_state.clone()).await;
+    dummy_node.shutdown().await;
+    // the Tangle is not walked again while the snapshot is fresh
+    let cached_response = mempool(request, rosetta_config, server_state).await;
+
+    let hashes = |response: MempoolResponse| {
+        response
+            .transaction_identifiers
+            .into_iter()
+            .map(|transaction_identifier| transaction_identifier.hash)
+            .collect::<Vec<_>>()
+    };
+    let response = hashes(response.unwrap());
+    assert_eq!(2, response.len());
+    assert_eq!(response, hashes(cached_response.unwrap()));
+}
+
+#[tokio::test]
+#[should_panic]
+#[serial]
+async fn wrong_network() {
+    let request = MempoolRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: WRONG_NETWORK.to_string(),
+        },
+    };
+
+    test_request(Request::Mempool(request))
+        .await
+        .unwrap_mempool_response()
+        .unwrap();
+}
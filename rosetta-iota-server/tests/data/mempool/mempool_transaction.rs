@@ -0,0 +1,80 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{VALID_BLOCKCHAIN, VALID_NETWORK, WRONG_NETWORK},
+    test_request, Request,
+};
+
+use rosetta_iota_server::{
+    data::mempool::mempool_transaction::*,
+    types::{NetworkIdentifier, TransactionIdentifier},
+};
+
+use serial_test::serial;
+
+// the transaction of the pending message of the dummy node
+const PENDING_TRANSACTION_ID: &str = "95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e7";
+
+fn mempool_transaction_request(network: &str, transaction_hash: &str) -> MempoolTransactionRequest {
+    MempoolTransactionRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: network.to_string(),
+        },
+        transaction_identifier: TransactionIdentifier {
+            hash: transaction_hash.to_string(),
+        },
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn valid_request() {
+    let request = mempool_transaction_request(VALID_NETWORK, PENDING_TRANSACTION_ID);
+
+    let response = test_request(Request::MempoolTransaction(request))
+        .await
+        .unwrap_mempool_transaction_response()
+        .unwrap();
+
+    assert_eq!(PENDING_TRANSACTION_ID, response.transaction.transaction_identifier.hash);
+    // one input and 16 outputs, none of them applied to the ledger yet
+    assert_eq!(17, response.transaction.operations.len());
+    assert_eq!("INPUT", response.transaction.operations[0].type_);
+    assert!(response
+        .transaction
+        .operations
+        .iter()
+        .all(|operation| operation.status.is_none()));
+}
+
+#[tokio::test]
+#[serial]
+async fn transaction_not_in_mempool() {
+    let request = mempool_transaction_request(
+        VALID_NETWORK,
+        "dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f",
+    );
+
+    let error = test_request(Request::MempoolTransaction(request))
+        .await
+        .unwrap_mempool_transaction_response()
+        .unwrap_err();
+
+    assert_eq!(1, error.code());
+    assert!(!error.retriable());
+}
+
+#[tokio::test]
+#[serial]
+async fn wrong_network() {
+    let request = mempool_transaction_request(WRONG_NETWORK, PENDING_TRANSACTION_ID);
+
+    let error = test_request(Request::MempoolTransaction(request))
+        .await
+        .unwrap_mempool_transaction_response()
+        .unwrap_err();
+
+    assert_eq!(3, error.code());
+}
@@ -0,0 +1,5 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod mempool_handler;
+mod mempool_transaction;
@@ -3,4 +3,8 @@
 
 mod account;
 mod block;
+mod call;
+mod events;
+mod mempool;
 mod network;
+mod search;
@@ -27,6 +27,8 @@ async fn valid_request() {
 
     assert_eq!("1.4.10", response.version.rosetta_version);
     assert_eq!("1.0.5", response.version.node_version);
+    // nothing was followed yet
+    assert!(response.version.metadata.unwrap().indexed_blocks.is_none());
 
     assert_eq!("Success", response.allow.operation_statuses[0].status);
     assert!(response.allow.operation_statuses[0].successful);
@@ -34,11 +36,52 @@ async fn valid_request() {
     assert_eq!("INPUT", response.allow.operation_types[0]);
     assert_eq!("SIG_LOCKED_SINGLE_OUTPUT", response.allow.operation_types[1]);
     assert_eq!("SIG_LOCKED_DUST_ALLOWANCE_OUTPUT", response.allow.operation_types[2]);
+    assert_eq!("TREASURY_INPUT", response.allow.operation_types[3]);
+    assert_eq!("TREASURY_OUTPUT", response.allow.operation_types[4]);
+
+    assert_eq!(
+        vec![
+            "get_message",
+            "get_output",
+            "get_milestone",
+            "node_info",
+            "dust_allowed",
+            "find_block_by_timestamp"
+        ],
+        response.allow.call_methods
+    );
 
     assert_eq!(1, response.allow.errors[0].code);
     assert_eq!("non retriable error", response.allow.errors[0].message);
     assert!(!response.allow.errors[0].retriable);
     assert!(response.allow.errors[0].details.is_none());
+
+    assert!(!response.allow.historical_balance_lookup);
+    assert!(response.allow.mempool_coins);
+}
+
+#[tokio::test]
+#[serial]
+async fn lists_every_error_code() {
+    let request = NetworkOptionsRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+    };
+
+    let response = test_request(Request::NetworkOptions(request))
+        .await
+        .unwrap_network_options_response()
+        .unwrap();
+
+    let codes: Vec<u64> = response.allow.errors.iter().map(|e| e.code).collect();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], codes);
+
+    assert_eq!("request was made for wrong network", response.allow.errors[2].message);
+    assert!(!response.allow.errors[2].retriable);
+    assert_eq!("node unreachable", response.allow.errors[8].message);
+    assert!(response.allow.errors[8].retriable);
 }
 
 #[tokio::test]
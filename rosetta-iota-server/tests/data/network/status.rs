@@ -2,34 +2,124 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    config::{VALID_BLOCKCHAIN, VALID_NETWORK, WRONG_BLOCKCHAIN, WRONG_NETWORK},
+    config::{default_rosetta_config, VALID_BLOCKCHAIN, VALID_NETWORK, WRONG_BLOCKCHAIN, WRONG_NETWORK},
+    dummy_node::start_dummy_node,
     test_request, Request,
 };
 
-use rosetta_iota_server::{data::network::status::NetworkStatusRequest, types::NetworkIdentifier};
+use rosetta_iota_server::{
+    config::RosettaConfig,
+    data::network::status::{network_status, NetworkStatusRequest, NetworkStatusResponse},
+    error::ApiError,
+    state::ServerState,
+    types::NetworkIdentifier,
+};
 
 use serial_test::serial;
 
-#[tokio::test]
-#[serial]
-async fn valid_request() {
-    let request = NetworkStatusRequest {
+fn status_request() -> NetworkStatusRequest {
+    NetworkStatusRequest {
         network_identifier: NetworkIdentifier {
             blockchain: VALID_BLOCKCHAIN.to_string(),
             network: VALID_NETWORK.to_string(),
         },
-    };
+    }
+}
 
-    let response = test_request(Request::NetworkStatus(request))
+async fn status_with_state(
+    rosetta_config: RosettaConfig,
+    server_state: ServerState,
+) -> Result<NetworkStatusResponse, ApiError> {
+    let dummy_node = start_dummy_node().await;
+    let response = network_status(status_request(), rosetta_config, server_state).await;
+    dummy_node.shutdown().await;
+    response
+}
+
+#[tokio::test]
+#[serial]
+async fn valid_request() {
+    let response = test_request(Request::NetworkStatus(status_request()))
         .await
         .unwrap_network_status_response()
         .unwrap();
 
     assert_eq!(1438448, response.current_block_identifier.index);
-    assert_eq!("1438448", response.current_block_identifier.hash);
+    assert_eq!(
+        "8abc7c6b1b871a2bf6c5112d9bd0d7f310176fbe35127be269006bc1becc94e4",
+        response.current_block_identifier.hash
+    );
+    // the default genesis milestone 1 was pruned by the dummy node, and its ID is not known
+    assert_eq!(1, response.genesis_block_identifier.index);
+    assert_eq!("0".repeat(64), response.genesis_block_identifier.hash);
+    // milestone 1438001 is the first one the node did not prune, but the ID of its parent is not known
+    assert_eq!(1438002, response.oldest_block_identifier.index);
+    assert_eq!(format!("{:064x}", 1438002), response.oldest_block_identifier.hash);
     assert_eq!(1634052071000, response.current_block_timestamp);
 }
 
+#[tokio::test]
+#[serial]
+async fn genesis_after_pruning_index() {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.genesis_index = Some(1438001);
+    let server_state = ServerState::new(&rosetta_config);
+
+    let response = status_with_state(rosetta_config, server_state).await.unwrap();
+
+    assert_eq!(
+        "d4f7593226bc2300562ce774ed8044cf31e55d928b7acfbec5cf16368674d758",
+        response.genesis_block_identifier.hash
+    );
+    assert_eq!(
+        "d4f7593226bc2300562ce774ed8044cf31e55d928b7acfbec5cf16368674d758",
+        response.oldest_block_identifier.hash
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn oldest_block_has_known_parent() {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.genesis_index = Some(1437000);
+    let server_state = ServerState::new(&rosetta_config);
+    server_state.block_hashes.insert(1437000, format!("{:064x}", 1437000));
+
+    let response = status_with_state(rosetta_config, server_state).await.unwrap();
+
+    assert_eq!(format!("{:064x}", 1437000), response.genesis_block_identifier.hash);
+    // milestone 1438001 is the first one the node did not prune, but the ID of its parent is not known
+    assert_eq!(1438002, response.oldest_block_identifier.index);
+}
+
+#[tokio::test]
+#[serial]
+async fn configured_genesis() {
+    let mut rosetta_config = default_rosetta_config();
+    rosetta_config.genesis_index = Some(1438447);
+    let server_state = ServerState::new(&rosetta_config);
+
+    let request = NetworkStatusRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+    };
+
+    let dummy_node = start_dummy_node().await;
+    let response = network_status(request, rosetta_config, server_state).await;
+    dummy_node.shutdown().await;
+    let response = response.unwrap();
+
+    assert_eq!(1438447, response.genesis_block_identifier.index);
+    assert_eq!(
+        "0730a63753e494d8d90adb0ca15c8b0192a748da84df48b3099ef1338558f3e6",
+        response.genesis_block_identifier.hash
+    );
+    // the node still has older milestones, but nothing before the genesis is served
+    assert_eq!(1438447, response.oldest_block_identifier.index);
+}
+
 #[tokio::test]
 #[should_panic]
 #[serial]
@@ -0,0 +1,130 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{default_rosetta_config, VALID_BLOCKCHAIN, VALID_NETWORK},
+    dummy_node::start_dummy_node,
+};
+
+use rosetta_iota_server::{
+    data::search::transactions::*,
+    follower::sync_milestones,
+    state::ServerState,
+    types::{CoinIdentifier, NetworkIdentifier},
+    RosettaConfig,
+};
+
+use serial_test::serial;
+
+const SPENT_COIN: &str = "dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f0d00";
+
+fn search_request() -> SearchTransactionsRequest {
+    SearchTransactionsRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: VALID_BLOCKCHAIN.to_string(),
+            network: VALID_NETWORK.to_string(),
+        },
+        operator: None,
+        max_block: None,
+        offset: None,
+        limit: None,
+        transaction_identifier: None,
+        account_identifier: None,
+        coin_identifier: None,
+        type_: None,
+        status: None,
+        success: None,
+    }
+}
+
+async fn followed_server_state(rosetta_config: &RosettaConfig) -> ServerState {
+    let server_state = ServerState::new(rosetta_config);
+
+    let dummy_node = start_dummy_node().await;
+    sync_milestones(&server_state, rosetta_config).await.unwrap();
+    dummy_node.shutdown().await;
+
+    server_state
+}
+
+#[tokio::test]
+#[serial]
+async fn search_by_coin_and_type() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = followed_server_state(&rosetta_config).await;
+
+    let mut request = search_request();
+    request.coin_identifier = Some(CoinIdentifier {
+        identifier: SPENT_COIN.to_string(),
+    });
+    request.type_ = Some("INPUT".to_string());
+    request.success = Some(true);
+
+    let response = search_transactions(request, rosetta_config, server_state)
+        .await
+        .unwrap();
+
+    assert_eq!(1, response.total_count);
+    assert_eq!(1438448, response.transactions[0].block_identifier.index);
+    assert_eq!(17, response.transactions[0].transaction.operations.len());
+    assert!(response.next_offset.is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn search_below_max_block() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = followed_server_state(&rosetta_config).await;
+
+    let mut request = search_request();
+    request.coin_identifier = Some(CoinIdentifier {
+        identifier: SPENT_COIN.to_string(),
+    });
+    request.max_block = Some(1438447);
+
+    let response = search_transactions(request, rosetta_config, server_state)
+        .await
+        .unwrap();
+
+    assert_eq!(0, response.total_count);
+    assert!(response.transactions.is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn search_outside_indexed_blocks() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = followed_server_state(&rosetta_config).await;
+
+    // the follower indexes the milestones after the pruning index up to the confirmed one
+    for (max_block, code) in [(1438000, 1), (1438449, 2)].iter() {
+        let mut request = search_request();
+        request.max_block = Some(*max_block);
+
+        let error = search_transactions(request, rosetta_config.clone(), server_state.clone())
+            .await
+            .unwrap_err();
+
+        assert_eq!(*code, error.code());
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn search_pages() {
+    let rosetta_config = default_rosetta_config();
+    let server_state = followed_server_state(&rosetta_config).await;
+
+    let mut request = search_request();
+    request.offset = Some(1);
+    request.limit = Some(1);
+
+    let response = search_transactions(request, rosetta_config, server_state)
+        .await
+        .unwrap();
+
+    // milestone 1438448 contains a regular and a conflicting transaction
+    assert_eq!(2, response.total_count);
+    assert_eq!(1, response.transactions.len());
+    assert!(response.next_offset.is_none());
+}
@@ -1,6 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use lazy_static::lazy_static;
 use tokio::{
     sync::{
         oneshot,
@@ -8,16 +9,50 @@ use tokio::{
     },
     time::Duration,
 };
-use warp::Filter;
+use warp::{http::StatusCode, Filter};
 
-use crate::config::DUMMY_NODE_BIND_ADDR;
+use crate::config::{
+    BAD_REQUEST_MILESTONE_INDEX, CONFLICTING_MESSAGE_ID, DUMMY_NODE_BIND_ADDR, DUST_ADDRESS, DUST_TRANSACTION_ID,
+    FAILING_MILESTONE_INDEX, INDEXATION_MESSAGE_ID, INDEXATION_MILESTONE_INDEX, INDEXATION_TRANSACTION_ID,
+    MISSING_INPUT_MESSAGE_ID, MISSING_OUTPUT_ID, MULTI_TRANSACTION_MILESTONE_INDEX, PENDING_MESSAGE_ID,
+    PENDING_SPEND_MESSAGE_ID,
+};
+
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+lazy_static! {
+    // the outputs and messages that were requested from the dummy nodes, as "outputs/<id>" and "messages/<id>"
+    static ref REQUESTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+}
 
-use std::net::SocketAddr;
+// the number of node info requests to the dummy nodes
+static INFO_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the outputs and messages that were requested since the last call.
+pub fn take_requests() -> Vec<String> {
+    std::mem::take(&mut *REQUESTS.lock().unwrap())
+}
+
+/// Returns the number of node info requests since the last call.
+pub fn take_info_requests() -> usize {
+    INFO_REQUESTS.swap(0, Ordering::Relaxed)
+}
 
 pub async fn start_dummy_node() -> DummyNodeHandle {
+    start_dummy_node_at(DUMMY_NODE_BIND_ADDR).await
+}
+
+pub async fn start_dummy_node_at(bind_addr: &str) -> DummyNodeHandle {
+    let bind_addr = bind_addr.parse::<SocketAddr>().unwrap();
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let (return_tx, return_rx) = oneshot::channel();
-    tokio::task::spawn(run_server(shutdown_rx, return_tx));
+    tokio::task::spawn(run_server(bind_addr, shutdown_rx, return_tx));
     // sleep some time to make sure the dummy node is up
     tokio::time::sleep(Duration::from_millis(100)).await;
     DummyNodeHandle { shutdown_tx, return_rx }
@@ -37,16 +72,82 @@ impl DummyNodeHandle {
     }
 }
 
-async fn run_server(shutdown_rx: Receiver<()>, return_tx: Sender<()>) {
-    let bind_addr = DUMMY_NODE_BIND_ADDR.to_string().parse::<SocketAddr>().unwrap();
+// index, message id and timestamp of the milestones the dummy node knows
+pub const MILESTONES: [(u32, &str, u64); 5] = [
+    (
+        1438001,
+        "d4f7593226bc2300562ce774ed8044cf31e55d928b7acfbec5cf16368674d758",
+        1634047601,
+    ),
+    (
+        1438441,
+        "feefb290f9d0d467f81523483a82d80827d4e0eb8f2c0514c1805185ca0690aa",
+        1634052001,
+    ),
+    (
+        1438447,
+        "0730a63753e494d8d90adb0ca15c8b0192a748da84df48b3099ef1338558f3e6",
+        1634052061,
+    ),
+    (
+        1438448,
+        "8abc7c6b1b871a2bf6c5112d9bd0d7f310176fbe35127be269006bc1becc94e4",
+        1634052071,
+    ),
+    (
+        1438495,
+        "0a3060a16bb7a3671e446f3cbb524940cc377d4b0ccd934911cc378dc263cfd1",
+        1634052541,
+    ),
+];
 
+/// Returns index and timestamp of a milestone between the listed ones.
+fn unlisted_milestone(index: &str) -> Option<(u32, u64)> {
+    let index = index.parse::<u32>().ok()?;
+    let (first_index, _, first_timestamp) = MILESTONES[0];
+    let (last_index, _, _) = MILESTONES[MILESTONES.len() - 1];
+    if index < first_index || index > last_index {
+        return None;
+    }
+    Some((index, first_timestamp + (index - first_index) as u64 * 10))
+}
+
+/// Returns index and JSON of the outputs of DUST_TRANSACTION_ID, given the packed output index.
+fn dust_output(output_index: &str) -> Option<(u16, &'static str)> {
+    match output_index {
+        "0000" => Some((
+            0,
+            r#"{"type":0,"address":{"type":0,"address":"14495a157f4bfb82e99dad269823cac7eea9c28bf7ae8e3d8ac3d748d5f0f871"},"amount":1}"#,
+        )),
+        "0100" => Some((
+            1,
+            r#"{"type":0,"address":{"type":0,"address":"14495a157f4bfb82e99dad269823cac7eea9c28bf7ae8e3d8ac3d748d5f0f871"},"amount":500000}"#,
+        )),
+        "0200" => Some((
+            2,
+            r#"{"type":1,"address":{"type":0,"address":"14495a157f4bfb82e99dad269823cac7eea9c28bf7ae8e3d8ac3d748d5f0f871"},"amount":1000000}"#,
+        )),
+        _ => None,
+    }
+}
+
+async fn run_server(bind_addr: SocketAddr, shutdown_rx: Receiver<()>, return_tx: Sender<()>) {
     let info = warp::path!("api" / "v1" / "info").map(|| {
+        INFO_REQUESTS.fetch_add(1, Ordering::Relaxed);
         r#"{"data":{"name":"HORNET","version":"1.0.5","isHealthy":true,"networkId":"chrysalis-mainnet","bech32HRP":"iota","minPoWScore":4000,"messagesPerSecond":12,"referencedMessagesPerSecond":11,"referencedRate":91.66666666666666,"latestMilestoneTimestamp":1635331891,"latestMilestoneIndex":1438449,"confirmedMilestoneIndex":1438448,"pruningIndex":1438000,"features":["PoW"]}}"#
     });
 
-    let address = warp::path!("api" / "v1" / "addresses" / String).map(|address| {
+    // every dummy node is at another ledger index, like synced nodes that are a few milestones apart
+    let ledger_index = 1438441
+        + bind_addr
+            .port()
+            .saturating_sub(DUMMY_NODE_BIND_ADDR.parse::<SocketAddr>().unwrap().port()) as u32;
+    let address = warp::path!("api" / "v1" / "addresses" / String).map(move |address| {
         if address == "iota1qp6gwwy7rruk0d3j9fqzcxnfrstfedk2m65jst2tx7xmkad4agjc5r7ptjz" {
-            r#"{"data":{"addressType":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a","balance":20651169480,"dustAllowed":false,"ledgerIndex":1438441}}"#
+            format!(
+                r#"{{"data":{{"addressType":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a","balance":20651169480,"dustAllowed":false,"ledgerIndex":{}}}}}"#,
+                ledger_index
+            )
         } else {
             unimplemented!()
         }
@@ -54,23 +155,88 @@ async fn run_server(shutdown_rx: Receiver<()>, return_tx: Sender<()>) {
 
     let address_outputs = warp::path!("api" / "v1" / "addresses" / String / "outputs").map(|address| {
         if address == "iota1qp6gwwy7rruk0d3j9fqzcxnfrstfedk2m65jst2tx7xmkad4agjc5r7ptjz" {
-            r#"{"data":{"addressType":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a","maxResults":1000,"count":1,"outputIds":["d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000"],"ledgerIndex":1438495}}"#
+            r#"{"data":{"addressType":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a","maxResults":1000,"count":1,"outputIds":["d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000"],"ledgerIndex":1438495}}"#.to_string()
+        } else if address == DUST_ADDRESS {
+            format!(
+                r#"{{"data":{{"addressType":0,"address":"14495a157f4bfb82e99dad269823cac7eea9c28bf7ae8e3d8ac3d748d5f0f871","maxResults":1000,"count":3,"outputIds":["{0}0000","{0}0100","{0}0200"],"ledgerIndex":1438495}}}}"#,
+                DUST_TRANSACTION_ID
+            )
         } else {
             unimplemented!()
         }
     });
 
-    let outputs = warp::path!("api" / "v1" / "outputs" / String).map(|output_id| {
+    let outputs = warp::path!("api" / "v1" / "outputs" / String).map(|output_id: String| {
+        REQUESTS.lock().unwrap().push(format!("outputs/{}", output_id));
         if output_id == "d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000" {
-            r#"{"data":{"messageId":"1c7a3c3f262dc1bb75adf4709d73cb73d6de7c3616ed6fa6ae44efbb38ea522b","transactionId":"d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b5019","outputIndex":0,"isSpent":false,"ledgerIndex":1438614,"output":{"type":0,"address":{"type":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a"},"amount":20651169480}}}"#
+            warp::reply::with_status(
+                r#"{"data":{"messageId":"1c7a3c3f262dc1bb75adf4709d73cb73d6de7c3616ed6fa6ae44efbb38ea522b","transactionId":"d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b5019","outputIndex":0,"isSpent":false,"ledgerIndex":1438614,"output":{"type":0,"address":{"type":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a"},"amount":20651169480}}}"#.to_string(),
+                StatusCode::OK,
+            )
+        } else if output_id == "dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f0d00" {
+            warp::reply::with_status(
+                r#"{"data":{"messageId":"e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb","transactionId":"dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f","outputIndex":13,"isSpent":true,"ledgerIndex":1438614,"output":{"type":0,"address":{"type":0,"address":"eda2fcdc37e4d4640bf2f9862da9b46e360fe4f0c42d51d4f2f3a68d31c98309"},"amount":100796233521054}}}"#.to_string(),
+                StatusCode::OK,
+            )
+        } else if output_id == "95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e70c00" {
+            warp::reply::with_status(
+                r#"{"data":{"messageId":"9589741dfb651adc6c240f326d75cf1bfe92fcb7cbaf203cd7c5b1a5df2a1f0d","transactionId":"95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e7","outputIndex":12,"isSpent":true,"ledgerIndex":1438614,"output":{"type":0,"address":{"type":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a"},"amount":10000000000}}}"#.to_string(),
+                StatusCode::OK,
+            )
+        } else if output_id == format!("{}0000", INDEXATION_TRANSACTION_ID) {
+            warp::reply::with_status(
+                format!(
+                    r#"{{"data":{{"messageId":"{}","transactionId":"{}","outputIndex":0,"isSpent":false,"ledgerIndex":1438614,"output":{{"type":0,"address":{{"type":0,"address":"eda2fcdc37e4d4640bf2f9862da9b46e360fe4f0c42d51d4f2f3a68d31c98309"}},"amount":100796233521054}}}}}}"#,
+                    INDEXATION_MESSAGE_ID, INDEXATION_TRANSACTION_ID
+                ),
+                StatusCode::OK,
+            )
+        } else if let Some(output) = output_id.strip_prefix(DUST_TRANSACTION_ID).and_then(dust_output) {
+            warp::reply::with_status(
+                format!(
+                    r#"{{"data":{{"messageId":"e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb","transactionId":"{}","outputIndex":{},"isSpent":false,"ledgerIndex":1438614,"output":{}}}}}"#,
+                    DUST_TRANSACTION_ID, output.0, output.1
+                ),
+                StatusCode::OK,
+            )
+        } else if output_id == MISSING_OUTPUT_ID {
+            warp::reply::with_status(
+                r#"{"error":{"code":"404","message":"output not found"}}"#.to_string(),
+                StatusCode::NOT_FOUND,
+            )
         } else {
             unimplemented!()
         }
     });
 
-    let milestones = warp::path!("api" / "v1" / "milestones" / String).map(|index| {
-        if index == "1438448" {
-            r#"{"data":{"index":1438448,"messageId":"8abc7c6b1b871a2bf6c5112d9bd0d7f310176fbe35127be269006bc1becc94e4","timestamp":1634052071}}"#
+    let milestones = warp::path!("api" / "v1" / "milestones" / String).map(|index: String| {
+        if let Some((index, message_id, timestamp)) = MILESTONES.iter().find(|(i, _, _)| i.to_string() == index) {
+            warp::reply::with_status(
+                format!(
+                    r#"{{"data":{{"index":{},"messageId":"{}","timestamp":{}}}}}"#,
+                    index, message_id, timestamp
+                ),
+                StatusCode::OK,
+            )
+        } else if let Some((index, timestamp)) = unlisted_milestone(&index) {
+            // the listed milestones are 10 seconds apart, the ones in between follow the same pace
+            warp::reply::with_status(
+                format!(
+                    r#"{{"data":{{"index":{},"messageId":"{:064x}","timestamp":{}}}}}"#,
+                    index, index, timestamp
+                ),
+                StatusCode::OK,
+            )
+        } else if index == FAILING_MILESTONE_INDEX.to_string() {
+            warp::reply::with_status(
+                r#"{"error":{"code":"500","message":"internal server error"}}"#.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        } else if index == BAD_REQUEST_MILESTONE_INDEX.to_string() {
+            warp::reply::with_status(
+                r#"{"error":{"code":"400","message":"invalid milestone index"}}"#.to_string(),
+                StatusCode::BAD_REQUEST,
+            )
         } else {
             unimplemented!()
         }
@@ -78,7 +244,24 @@ async fn run_server(shutdown_rx: Receiver<()>, return_tx: Sender<()>) {
 
     let utxo_changes = warp::path!("api" / "v1" / "milestones" / u32 / "utxo-changes").map(|milestone_index| {
         if milestone_index == 1438448 {
-            r#"{"data":{"index":68910,"createdOutputs":["d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000"],"consumedOutputs":["95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e70c00"]}}"#
+            r#"{"data":{"index":68910,"createdOutputs":["d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000"],"consumedOutputs":["95535a4cc1976149d11a6e0b988118d1de435c50481749e351ef835d705ab1e70c00"]}}"#.to_string()
+        } else if milestone_index == INDEXATION_MILESTONE_INDEX {
+            format!(
+                r#"{{"data":{{"index":{},"createdOutputs":["{}0000"],"consumedOutputs":[]}}}}"#,
+                milestone_index, INDEXATION_TRANSACTION_ID
+            )
+        } else if milestone_index == MULTI_TRANSACTION_MILESTONE_INDEX {
+            // not ordered by transaction
+            format!(
+                r#"{{"data":{{"index":{},"createdOutputs":["{}0000","d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b50190000"],"consumedOutputs":[]}}}}"#,
+                milestone_index, INDEXATION_TRANSACTION_ID
+            )
+        } else if unlisted_milestone(&milestone_index.to_string()).is_some() {
+            // the other milestones do not change the ledger
+            format!(
+                r#"{{"data":{{"index":{},"createdOutputs":[],"consumedOutputs":[]}}}}"#,
+                milestone_index
+            )
         } else {
             unimplemented!()
         }
@@ -88,16 +271,76 @@ async fn run_server(shutdown_rx: Receiver<()>, return_tx: Sender<()>) {
         r#"{"data":[{"id":"A","multiAddresses":["/dns/chrysalis-nodes.iota.org/tcp/15600"],"alias":"A","relation":"known","connected":true,"gossip":{"heartbeat":{"solidMilestoneIndex":69082,"prunedMilestoneIndex":0,"latestMilestoneIndex":69082,"connectedNeighbors":1,"syncedNeighbors":8},"metrics":{"newMessages":10139612,"knownMessages":1210097,"receivedMessages":11538518,"receivedMessageRequests":0,"receivedMilestoneRequests":1,"receivedHeartbeats":54797,"sentMessages":564098,"sentMessageRequests":2758,"sentMilestoneRequests":9,"sentHeartbeats":54807,"droppedPackets":0}}}]}"#
     });
 
-    let messages = warp::path!("api" / "v1" / "messages" / String).map(|message_id| {
+    let messages = warp::path!("api" / "v1" / "messages" / String).map(|message_id: String| {
+        REQUESTS.lock().unwrap().push(format!("messages/{}", message_id));
         if message_id == "1c7a3c3f262dc1bb75adf4709d73cb73d6de7c3616ed6fa6ae44efbb38ea522b" || message_id == "70a9a9bc408121b766cc20d9a5b8dba0829e41244c500b2d04cf34f1f20f4621" {
             r#"{"data":{"networkId":"14379272398717627559","parentMessageIds":["542d6f263b1d3917a40b09b844984a4a7f7800ef6fd2b52500df80880c0ac1d9","7f3821b3f6429a596fe2f27efef99aa3ca43320bb666cfe32c6d81aceb271700","9589741dfb651adc6c240f326d75cf1bfe92fcb7cbaf203cd7c5b1a5df2a1f0d","e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb"],"payload":{"type":0,"essence":{"type":0,"inputs":[{"type":0,"transactionId":"dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f","transactionOutputIndex":13}],"outputs":[{"type":0,"address":{"type":0,"address":"14495a157f4bfb82e99dad269823cac7eea9c28bf7ae8e3d8ac3d748d5f0f871"},"amount":10000000},{"type":0,"address":{"type":0,"address":"1a99ca850eaf419acfb043501b94bfa36aff225f2775dd93936c92d5712e747b"},"amount":10000000},{"type":0,"address":{"type":0,"address":"3a3a93a3fa38cd9b3e850f64bee11d17c3eb645b290a4b0a1aa7cdf5855c5216"},"amount":10000000},{"type":0,"address":{"type":0,"address":"4aa1130ce85e5ca427a379f3cd061f93f5e059aa6a65c02780cf048254aff67e"},"amount":10000000},{"type":0,"address":{"type":0,"address":"4cacc90985df6589da267451c844481feb2184ab5333cd40a4d017856c77439b"},"amount":10000000},{"type":0,"address":{"type":0,"address":"7357c7ac6a8c2019ee1dedd1d2ac78545367a2dd10ce2620100d24880ba97404"},"amount":10000000},{"type":0,"address":{"type":0,"address":"73bd5034a902eb2f77b3687908a8c7e184e114b866b7fc87fb34aa977a70a373"},"amount":10000000},{"type":0,"address":{"type":0,"address":"80598b362fc1d5c7a350604794e7f7826d4041dbfb734607fe15695212c8abe4"},"amount":10000000},{"type":0,"address":{"type":0,"address":"9eaa14137fbe57d47d5f959ec03a16abb863e6dee6891cef5ac16564cc8d2051"},"amount":10000000},{"type":0,"address":{"type":0,"address":"a3a4afeb121479b838071622f3a0a3a371403cee58ad9144b7c2776f64d52008"},"amount":10000000},{"type":0,"address":{"type":0,"address":"a983b259d839fa7f10b1cb83a3e8f007290b1623fd3e1bcaeb7e31638d6b70e3"},"amount":10000000},{"type":0,"address":{"type":0,"address":"b67617ea177376281e5fb865c686fecd17c342b98546254a8659333dfee9ba80"},"amount":10000000},{"type":0,"address":{"type":0,"address":"d6b3d2cc85f3b82ce9d9e8073b775c46482282dd83411bc554fe12bc41ca573f"},"amount":10000000},{"type":0,"address":{"type":0,"address":"eda2fcdc37e4d4640bf2f9862da9b46e360fe4f0c42d51d4f2f3a68d31c98309"},"amount":100796083521054},{"type":0,"address":{"type":0,"address":"f3fa64cb5a1e11b420492db46cd8774c5213c629fac7d98a42f1b1964675ff73"},"amount":10000000},{"type":0,"address":{"type":0,"address":"f616c401d9eed517cc367665e2b90aa4e9c0a3cc2fee3b3b6f5eb76a2afc25b1"},"amount":10000000}],"payload":null},"unlockBlocks":[{"type":0,"signature":{"type":0,"publicKey":"35e5e3c0ad7a7b31837f7ed521f9acddf2381c8e1bad3c78107dec898b690a92","signature":"a5e0b6fc52a751c2b5d8b64acbd20e538e80de0e0b6646a2eb3091793f1e050458d11384d3185b94614ce22d1c9e23c5a909c0c3adbca037e705a236ae1a2f09"}}]},"nonce":"4611686018427745760"}}"#
+        } else if message_id == PENDING_SPEND_MESSAGE_ID {
+            r#"{"data":{"networkId":"14379272398717627559","parentMessageIds":["542d6f263b1d3917a40b09b844984a4a7f7800ef6fd2b52500df80880c0ac1d9","7f3821b3f6429a596fe2f27efef99aa3ca43320bb666cfe32c6d81aceb271700","9589741dfb651adc6c240f326d75cf1bfe92fcb7cbaf203cd7c5b1a5df2a1f0d","e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb"],"payload":{"type":0,"essence":{"type":0,"inputs":[{"type":0,"transactionId":"d2e2faaf394a5d22045668a55df27c9abe2057c1f2ce319999bed373269b5019","transactionOutputIndex":0}],"outputs":[{"type":0,"address":{"type":0,"address":"14495a157f4bfb82e99dad269823cac7eea9c28bf7ae8e3d8ac3d748d5f0f871"},"amount":1000000},{"type":0,"address":{"type":0,"address":"7487389e18f967b6322a402c1a691c169cb6cadea9282d4b378dbb75b5ea258a"},"amount":20650169480}],"payload":null},"unlockBlocks":[{"type":0,"signature":{"type":0,"publicKey":"35e5e3c0ad7a7b31837f7ed521f9acddf2381c8e1bad3c78107dec898b690a92","signature":"a5e0b6fc52a751c2b5d8b64acbd20e538e80de0e0b6646a2eb3091793f1e050458d11384d3185b94614ce22d1c9e23c5a909c0c3adbca037e705a236ae1a2f09"}}]},"nonce":"4611686018427745760"}}"#
+        } else if message_id == INDEXATION_MESSAGE_ID {
+            r#"{"data":{"networkId":"14379272398717627559","parentMessageIds":["542d6f263b1d3917a40b09b844984a4a7f7800ef6fd2b52500df80880c0ac1d9","7f3821b3f6429a596fe2f27efef99aa3ca43320bb666cfe32c6d81aceb271700","9589741dfb651adc6c240f326d75cf1bfe92fcb7cbaf203cd7c5b1a5df2a1f0d","e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb"],"payload":{"type":0,"essence":{"type":0,"inputs":[{"type":0,"transactionId":"dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f","transactionOutputIndex":13}],"outputs":[{"type":0,"address":{"type":0,"address":"eda2fcdc37e4d4640bf2f9862da9b46e360fe4f0c42d51d4f2f3a68d31c98309"},"amount":100796233521054}],"payload":{"type":2,"index":"726f7365747461","data":"6d656d6f"}},"unlockBlocks":[{"type":0,"signature":{"type":0,"publicKey":"35e5e3c0ad7a7b31837f7ed521f9acddf2381c8e1bad3c78107dec898b690a92","signature":"a5e0b6fc52a751c2b5d8b64acbd20e538e80de0e0b6646a2eb3091793f1e050458d11384d3185b94614ce22d1c9e23c5a909c0c3adbca037e705a236ae1a2f09"}}]},"nonce":"4611686018427745760"}}"#
+        } else if message_id == MISSING_INPUT_MESSAGE_ID {
+            r#"{"data":{"networkId":"14379272398717627559","parentMessageIds":["542d6f263b1d3917a40b09b844984a4a7f7800ef6fd2b52500df80880c0ac1d9","7f3821b3f6429a596fe2f27efef99aa3ca43320bb666cfe32c6d81aceb271700","9589741dfb651adc6c240f326d75cf1bfe92fcb7cbaf203cd7c5b1a5df2a1f0d","e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb"],"payload":{"type":0,"essence":{"type":0,"inputs":[{"type":0,"transactionId":"0000000000000000000000000000000000000000000000000000000000000000","transactionOutputIndex":0}],"outputs":[{"type":0,"address":{"type":0,"address":"eda2fcdc37e4d4640bf2f9862da9b46e360fe4f0c42d51d4f2f3a68d31c98309"},"amount":1000000}],"payload":null},"unlockBlocks":[{"type":0,"signature":{"type":0,"publicKey":"35e5e3c0ad7a7b31837f7ed521f9acddf2381c8e1bad3c78107dec898b690a92","signature":"a5e0b6fc52a751c2b5d8b64acbd20e538e80de0e0b6646a2eb3091793f1e050458d11384d3185b94614ce22d1c9e23c5a909c0c3adbca037e705a236ae1a2f09"}}]},"nonce":"4611686018427745760"}}"#
+        } else if message_id == CONFLICTING_MESSAGE_ID {
+            // spends the same output as the transaction above, without its last output
+            r#"{"data":{"networkId":"14379272398717627559","parentMessageIds":["542d6f263b1d3917a40b09b844984a4a7f7800ef6fd2b52500df80880c0ac1d9","7f3821b3f6429a596fe2f27efef99aa3ca43320bb666cfe32c6d81aceb271700","9589741dfb651adc6c240f326d75cf1bfe92fcb7cbaf203cd7c5b1a5df2a1f0d","e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb"],"payload":{"type":0,"essence":{"type":0,"inputs":[{"type":0,"transactionId":"dd2bd7ef1d67a6247823416337c2938a26ca91322ce89674a8795ac7b4072d2f","transactionOutputIndex":13}],"outputs":[{"type":0,"address":{"type":0,"address":"14495a157f4bfb82e99dad269823cac7eea9c28bf7ae8e3d8ac3d748d5f0f871"},"amount":10000000},{"type":0,"address":{"type":0,"address":"1a99ca850eaf419acfb043501b94bfa36aff225f2775dd93936c92d5712e747b"},"amount":10000000},{"type":0,"address":{"type":0,"address":"3a3a93a3fa38cd9b3e850f64bee11d17c3eb645b290a4b0a1aa7cdf5855c5216"},"amount":10000000},{"type":0,"address":{"type":0,"address":"4aa1130ce85e5ca427a379f3cd061f93f5e059aa6a65c02780cf048254aff67e"},"amount":10000000},{"type":0,"address":{"type":0,"address":"4cacc90985df6589da267451c844481feb2184ab5333cd40a4d017856c77439b"},"amount":10000000},{"type":0,"address":{"type":0,"address":"7357c7ac6a8c2019ee1dedd1d2ac78545367a2dd10ce2620100d24880ba97404"},"amount":10000000},{"type":0,"address":{"type":0,"address":"73bd5034a902eb2f77b3687908a8c7e184e114b866b7fc87fb34aa977a70a373"},"amount":10000000},{"type":0,"address":{"type":0,"address":"80598b362fc1d5c7a350604794e7f7826d4041dbfb734607fe15695212c8abe4"},"amount":10000000},{"type":0,"address":{"type":0,"address":"9eaa14137fbe57d47d5f959ec03a16abb863e6dee6891cef5ac16564cc8d2051"},"amount":10000000},{"type":0,"address":{"type":0,"address":"a3a4afeb121479b838071622f3a0a3a371403cee58ad9144b7c2776f64d52008"},"amount":10000000},{"type":0,"address":{"type":0,"address":"a983b259d839fa7f10b1cb83a3e8f007290b1623fd3e1bcaeb7e31638d6b70e3"},"amount":10000000},{"type":0,"address":{"type":0,"address":"b67617ea177376281e5fb865c686fecd17c342b98546254a8659333dfee9ba80"},"amount":10000000},{"type":0,"address":{"type":0,"address":"d6b3d2cc85f3b82ce9d9e8073b775c46482282dd83411bc554fe12bc41ca573f"},"amount":10000000},{"type":0,"address":{"type":0,"address":"eda2fcdc37e4d4640bf2f9862da9b46e360fe4f0c42d51d4f2f3a68d31c98309"},"amount":100796083521054},{"type":0,"address":{"type":0,"address":"f3fa64cb5a1e11b420492db46cd8774c5213c629fac7d98a42f1b1964675ff73"},"amount":10000000}],"payload":null},"unlockBlocks":[{"type":0,"signature":{"type":0,"publicKey":"35e5e3c0ad7a7b31837f7ed521f9acddf2381c8e1bad3c78107dec898b690a92","signature":"a5e0b6fc52a751c2b5d8b64acbd20e538e80de0e0b6646a2eb3091793f1e050458d11384d3185b94614ce22d1c9e23c5a909c0c3adbca037e705a236ae1a2f09"}}]},"nonce":"4611686018427745760"}}"#
         } else {
             unimplemented!()
         }
     });
 
+    let message_metadata = warp::path!("api" / "v1" / "messages" / String / "metadata").map(|message_id: String| {
+        if message_id == PENDING_MESSAGE_ID || message_id == PENDING_SPEND_MESSAGE_ID {
+            format!(
+                r#"{{"data":{{"messageId":"{}","parentMessageIds":["542d6f263b1d3917a40b09b844984a4a7f7800ef6fd2b52500df80880c0ac1d9","7f3821b3f6429a596fe2f27efef99aa3ca43320bb666cfe32c6d81aceb271700","9589741dfb651adc6c240f326d75cf1bfe92fcb7cbaf203cd7c5b1a5df2a1f0d","e7cd5c9d504976b615d45effa797bf3847d26b64841aec71e39f86e25c633acb"],"isSolid":true,"shouldPromote":false,"shouldReattach":false}}}}"#,
+                message_id
+            )
+        } else if message_id == CONFLICTING_MESSAGE_ID {
+            format!(
+                r#"{{"data":{{"messageId":"{}","parentMessageIds":[],"isSolid":true,"referencedByMilestoneIndex":1438448,"ledgerInclusionState":"conflicting","conflictReason":1}}}}"#,
+                message_id
+            )
+        } else if message_id == MILESTONES[3].1 {
+            // the conflicting message is part of the past cone of milestone 1438448
+            format!(
+                r#"{{"data":{{"messageId":"{}","parentMessageIds":["{}"],"isSolid":true,"referencedByMilestoneIndex":1438448,"ledgerInclusionState":"noTransaction"}}}}"#,
+                message_id, CONFLICTING_MESSAGE_ID
+            )
+        } else if message_id == format!("{:064x}", MULTI_TRANSACTION_MILESTONE_INDEX) {
+            format!(
+                r#"{{"data":{{"messageId":"{}","parentMessageIds":["{}"],"isSolid":true,"referencedByMilestoneIndex":{},"ledgerInclusionState":"noTransaction"}}}}"#,
+                message_id, MISSING_INPUT_MESSAGE_ID, MULTI_TRANSACTION_MILESTONE_INDEX
+            )
+        } else if message_id == MISSING_INPUT_MESSAGE_ID {
+            // the input of the transaction was never created
+            format!(
+                r#"{{"data":{{"messageId":"{}","parentMessageIds":[],"isSolid":true,"referencedByMilestoneIndex":{},"ledgerInclusionState":"conflicting","conflictReason":2}}}}"#,
+                message_id, MULTI_TRANSACTION_MILESTONE_INDEX
+            )
+        } else {
+            // every other message is already referenced by a milestone
+            format!(
+                r#"{{"data":{{"messageId":"{}","parentMessageIds":[],"isSolid":true,"referencedByMilestoneIndex":1438448,"ledgerInclusionState":"included"}}}}"#,
+                message_id
+            )
+        }
+    });
+
+    let treasury = warp::path!("api" / "v1" / "treasury").map(|| {
+        r#"{"data":{"milestoneId":"7c0a6a0c2b5bd2b38c9a6a0e4a4ff1c2b22b1e7d8c1e3a9f1b2c3d4e5f6a7b8c","amount":145115236385079}}"#
+    });
+
+    let tips = warp::path!("api" / "v1" / "tips").map(|| {
+        format!(
+            r#"{{"data":{{"tipMessageIds":["{}","{}"]}}}}"#,
+            PENDING_MESSAGE_ID, PENDING_SPEND_MESSAGE_ID
+        )
+    });
+
     let routes =
-        info.or(address.or(address_outputs.or(outputs.or(milestones.or(peers.or(utxo_changes.or(messages)))))));
+        info.or(address
+            .or(address_outputs.or(outputs
+                .or(milestones.or(peers.or(utxo_changes.or(messages.or(message_metadata.or(tips.or(treasury))))))))));
 
     println!("binding dummy node at {}", bind_addr);
 
@@ -0,0 +1,50 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use rosetta_iota_server::{error::ApiError, metrics};
+
+use serial_test::serial;
+use warp::{http::StatusCode, Filter};
+
+#[tokio::test]
+#[serial]
+async fn exposes_recorded_metrics() {
+    metrics::record_api_error(&ApiError::Retriable("node not synced".to_string()));
+    metrics::set_milestone_gap("http://localhost:14265", 1438448, 1438449);
+
+    let response = warp::test::request()
+        .method("GET")
+        .path("/metrics")
+        .reply(&metrics::routes())
+        .await;
+
+    assert_eq!(200, response.status());
+
+    let body = String::from_utf8(response.body().to_vec()).unwrap();
+    assert!(body.contains(r#"rosetta_api_errors_total{error="Retriable"}"#));
+    assert!(body.contains(r#"rosetta_node_milestone_gap{node="http://localhost:14265"} 1"#));
+}
+
+#[tokio::test]
+#[serial]
+async fn unknown_paths_share_one_route_label() {
+    // requests to unknown paths may fail with other status codes than 404, e.g. with a wrong method
+    let routes = metrics::routes()
+        .or(warp::any().map(|| warp::reply::with_status("", StatusCode::METHOD_NOT_ALLOWED)))
+        .with(warp::log::custom(metrics::record_request));
+
+    for path in &["/metrics", "/unknown/0", "/unknown/1"] {
+        warp::test::request().method("GET").path(path).reply(&routes).await;
+    }
+
+    let response = warp::test::request()
+        .method("GET")
+        .path("/metrics")
+        .reply(&metrics::routes())
+        .await;
+
+    let body = String::from_utf8(response.body().to_vec()).unwrap();
+    assert!(body.contains(r#"rosetta_requests_total{route="/metrics",status="200"}"#));
+    assert!(body.contains(r#"rosetta_requests_total{route="unmatched",status="405"} 2"#));
+    assert!(!body.contains("/unknown"));
+}
@@ -21,7 +21,16 @@ use rosetta_iota_server::{
             balance::{account_balance, AccountBalanceRequest, AccountBalanceResponse},
             coins::{account_coins, AccountCoinsRequest, AccountCoinsResponse},
         },
-        block::block_handler::{block, BlockRequest, BlockResponse},
+        block::{
+            block_handler::{block, BlockRequest, BlockResponse},
+            block_transaction::{block_transaction, BlockTransactionRequest, BlockTransactionResponse},
+        },
+        call::call_handler::{call, CallRequest, CallResponse},
+        events::blocks::{events_blocks, EventsBlocksRequest, EventsBlocksResponse},
+        mempool::{
+            mempool_handler::{mempool, MempoolRequest, MempoolResponse},
+            mempool_transaction::{mempool_transaction, MempoolTransactionRequest, MempoolTransactionResponse},
+        },
         network::{
             list::{network_list, NetworkListResponse},
             options::{network_options, NetworkOptionsRequest, NetworkOptionsResponse},
@@ -30,17 +39,25 @@ use rosetta_iota_server::{
     },
     error::ApiError,
     filters::EmptyRequest,
+    state::ServerState,
 };
 
+mod client;
 mod config;
 mod construction;
 mod data;
 mod dummy_node;
+mod metrics;
 
 pub enum Request {
     AccountBalance(AccountBalanceRequest),
     AccountCoins(AccountCoinsRequest),
     Block(BlockRequest),
+    BlockTransaction(BlockTransactionRequest),
+    Call(CallRequest),
+    EventsBlocks(EventsBlocksRequest),
+    Mempool(MempoolRequest),
+    MempoolTransaction(MempoolTransactionRequest),
     NetworkList(EmptyRequest),
     NetworkOptions(NetworkOptionsRequest),
     NetworkStatus(NetworkStatusRequest),
@@ -58,6 +75,11 @@ pub enum Response {
     AccountBalance(Result<AccountBalanceResponse, ApiError>),
     AccountCoins(Result<AccountCoinsResponse, ApiError>),
     Block(Result<BlockResponse, ApiError>),
+    BlockTransaction(Result<BlockTransactionResponse, ApiError>),
+    Call(Result<CallResponse, ApiError>),
+    EventsBlocks(Result<EventsBlocksResponse, ApiError>),
+    Mempool(Result<MempoolResponse, ApiError>),
+    MempoolTransaction(Result<MempoolTransactionResponse, ApiError>),
     NetworkList(Result<NetworkListResponse, ApiError>),
     NetworkOptions(Result<NetworkOptionsResponse, ApiError>),
     NetworkStatus(Result<NetworkStatusResponse, ApiError>),
@@ -86,6 +108,48 @@ impl Response {
             panic!("can not cast type")
         }
     }
+    fn unwrap_block_response(self) -> Result<BlockResponse, ApiError> {
+        if let Response::Block(r) = self {
+            r
+        } else {
+            panic!("can not cast type")
+        }
+    }
+    fn unwrap_block_transaction_response(self) -> Result<BlockTransactionResponse, ApiError> {
+        if let Response::BlockTransaction(r) = self {
+            r
+        } else {
+            panic!("can not cast type")
+        }
+    }
+    fn unwrap_call_response(self) -> Result<CallResponse, ApiError> {
+        if let Response::Call(r) = self {
+            r
+        } else {
+            panic!("can not cast type")
+        }
+    }
+    fn unwrap_events_blocks_response(self) -> Result<EventsBlocksResponse, ApiError> {
+        if let Response::EventsBlocks(r) = self {
+            r
+        } else {
+            panic!("can not cast type")
+        }
+    }
+    fn unwrap_mempool_response(self) -> Result<MempoolResponse, ApiError> {
+        if let Response::Mempool(r) = self {
+            r
+        } else {
+            panic!("can not cast type")
+        }
+    }
+    fn unwrap_mempool_transaction_response(self) -> Result<MempoolTransactionResponse, ApiError> {
+        if let Response::MempoolTransaction(r) = self {
+            r
+        } else {
+            panic!("can not cast type")
+        }
+    }
     fn unwrap_network_list_response(self) -> Result<NetworkListResponse, ApiError> {
         if let Response::NetworkList(r) = self {
             r
@@ -121,28 +185,53 @@ impl Response {
             panic!("can not cast type")
         }
     }
+    fn unwrap_construction_metadata_response(self) -> Result<ConstructionMetadataResponse, ApiError> {
+        if let Response::ConstructionMetadata(r) = self {
+            r
+        } else {
+            panic!("can not cast type")
+        }
+    }
 }
 
 pub async fn test_request(request: Request) -> Response {
     let rosetta_config = default_rosetta_config();
+    let server_state = ServerState::new(&rosetta_config);
 
     let dummy_node = start_dummy_node().await;
 
     let response = match request {
-        Request::AccountBalance(r) => Response::AccountBalance(account_balance(r, rosetta_config).await),
-        Request::AccountCoins(r) => Response::AccountCoins(account_coins(r, rosetta_config).await),
-        Request::Block(r) => Response::Block(block(r, rosetta_config).await),
-        Request::NetworkList(r) => Response::NetworkList(network_list(r, rosetta_config).await),
-        Request::NetworkOptions(r) => Response::NetworkOptions(network_options(r, rosetta_config).await),
-        Request::NetworkStatus(r) => Response::NetworkStatus(network_status(r, rosetta_config).await),
-        Request::ConstructionDerive(r) => Response::ConstructionDerive(derive(r, rosetta_config).await),
-        Request::ConstructionPreprocess(r) => Response::ConstructionPreprocess(preprocess(r, rosetta_config).await),
-        Request::ConstructionMetadata(r) => Response::ConstructionMetadata(metadata(r, rosetta_config).await),
-        Request::ConstructionParse(r) => Response::ConstructionParse(parse(r, rosetta_config).await),
-        Request::ConstructionPayloads(r) => Response::ConstructionPayloads(payloads(r, rosetta_config).await),
-        Request::ConstructionCombine(r) => Response::ConstructionCombine(combine(r, rosetta_config).await),
-        Request::ConstructionHash(r) => Response::ConstructionHash(hash(r, rosetta_config).await),
-        Request::ConstructionSubmit(r) => Response::ConstructionSubmit(submit(r, rosetta_config).await),
+        Request::AccountBalance(r) => Response::AccountBalance(account_balance(r, rosetta_config, server_state).await),
+        Request::AccountCoins(r) => Response::AccountCoins(account_coins(r, rosetta_config, server_state).await),
+        Request::Block(r) => Response::Block(block(r, rosetta_config, server_state).await),
+        Request::BlockTransaction(r) => {
+            Response::BlockTransaction(block_transaction(r, rosetta_config, server_state).await)
+        }
+        Request::Call(r) => Response::Call(call(r, rosetta_config, server_state).await),
+        Request::EventsBlocks(r) => Response::EventsBlocks(events_blocks(r, rosetta_config, server_state).await),
+        Request::Mempool(r) => Response::Mempool(mempool(r, rosetta_config, server_state).await),
+        Request::MempoolTransaction(r) => {
+            Response::MempoolTransaction(mempool_transaction(r, rosetta_config, server_state).await)
+        }
+        Request::NetworkList(r) => Response::NetworkList(network_list(r, rosetta_config, server_state).await),
+        Request::NetworkOptions(r) => Response::NetworkOptions(network_options(r, rosetta_config, server_state).await),
+        Request::NetworkStatus(r) => Response::NetworkStatus(network_status(r, rosetta_config, server_state).await),
+        Request::ConstructionDerive(r) => Response::ConstructionDerive(derive(r, rosetta_config, server_state).await),
+        Request::ConstructionPreprocess(r) => {
+            Response::ConstructionPreprocess(preprocess(r, rosetta_config, server_state).await)
+        }
+        Request::ConstructionMetadata(r) => {
+            Response::ConstructionMetadata(metadata(r, rosetta_config, server_state).await)
+        }
+        Request::ConstructionParse(r) => Response::ConstructionParse(parse(r, rosetta_config, server_state).await),
+        Request::ConstructionPayloads(r) => {
+            Response::ConstructionPayloads(payloads(r, rosetta_config, server_state).await)
+        }
+        Request::ConstructionCombine(r) => {
+            Response::ConstructionCombine(combine(r, rosetta_config, server_state).await)
+        }
+        Request::ConstructionHash(r) => Response::ConstructionHash(hash(r, rosetta_config, server_state).await),
+        Request::ConstructionSubmit(r) => Response::ConstructionSubmit(submit(r, rosetta_config, server_state).await),
     };
 
     dummy_node.shutdown().await;
@@ -4,7 +4,7 @@
 use crate::Config;
 
 use rosetta_iota_server::{
-    consts::iota_currency,
+    consts::{iota_currency, TREASURY_ACCOUNT},
     types::{AccountIdentifier, Currency},
 };
 
@@ -25,18 +25,40 @@ pub async fn balances_from_snapshot(config: &Config) {
         panic!("Can not find full_snapshot.bin file. Please re-setup rosetta-iota.")
     }
 
-    let (sep_index, balance_diffs) = {
-        let (sep_index, balance_diffs) = read_full_snapshot(full_path).await;
+    let (sep_index, balance_diffs, treasury) = {
+        let (sep_index, balance_diffs, treasury) = read_full_snapshot(full_path).await;
         if !delta_path.exists() {
             println!("Can not find delta_snapshot.bin file, continue nevertheless...");
-            (sep_index, balance_diffs)
+            (sep_index, balance_diffs, treasury)
         } else {
-            read_delta_snapshot(delta_path, balance_diffs).await
+            read_delta_snapshot(delta_path, balance_diffs, treasury).await
         }
     };
 
     save_sep_index(sep_index).await;
-    save_balance_diffs(balance_diffs, config).await;
+    save_balance_diffs(balance_diffs, treasury, config).await;
+}
+
+/// Returns the amount of the treasury before and after the receipt of the milestone, if it contains one.
+fn treasury_change(milestone: &MilestonePayload) -> Option<(u64, u64)> {
+    let receipt = match milestone.essence().receipt() {
+        Some(Payload::Receipt(receipt)) => receipt,
+        _ => return None,
+    };
+    let created_amount = match receipt.transaction() {
+        Payload::TreasuryTransaction(treasury_transaction) => match treasury_transaction.output() {
+            Output::Treasury(treasury_output) => treasury_output.amount(),
+            _ => panic!("unsupported output type of treasury transaction"),
+        },
+        _ => panic!("unsupported transaction type of receipt"),
+    };
+    let migrated_amount = receipt
+        .funds()
+        .iter()
+        .map(|funds_entry| funds_entry.output().amount())
+        .sum::<u64>();
+
+    Some((created_amount + migrated_amount, created_amount))
 }
 
 async fn import_milestone_diffs<R: Read>(
@@ -44,10 +66,12 @@ async fn import_milestone_diffs<R: Read>(
     mut ledger_index: MilestoneIndex,
     milestone_diff_count: u64,
     balance_diffs: &mut BalanceDiffs,
+    treasury: &mut u64,
 ) {
     for _ in 0..milestone_diff_count {
         let diff = MilestoneDiff::unpack(reader).expect("cannot unpack milestone diff");
         let index = diff.milestone().essence().index();
+        let treasury_change = treasury_change(diff.milestone());
         let mut tmp_balance_diffs = BalanceDiffs::new();
 
         for (_, output) in diff.created().iter() {
@@ -71,6 +95,8 @@ async fn import_milestone_diffs<R: Read>(
                         .dust_allowance_add(*output.address(), output.amount())
                         .expect("can not dust allowance");
                 }
+                // migrated funds are regular outputs, the treasury itself is tracked separately
+                Output::Treasury(_) => {}
                 _ => panic!("unsupported output type"),
             }
         }
@@ -96,6 +122,7 @@ async fn import_milestone_diffs<R: Read>(
                         .dust_allowance_sub(*output.address(), output.amount())
                         .expect("can not dust allowance");
                 }
+                Output::Treasury(_) => {}
                 _ => panic!("unsupported output type"),
             }
         }
@@ -105,6 +132,9 @@ async fn import_milestone_diffs<R: Read>(
                 balance_diffs
                     .merge(tmp_balance_diffs)
                     .expect("can not merge balance diffs");
+                if let Some((_, created_amount)) = treasury_change {
+                    *treasury = created_amount;
+                }
                 ledger_index = MilestoneIndex(*ledger_index + 1)
             }
             index if index == MilestoneIndex(*ledger_index) => {
@@ -112,6 +142,9 @@ async fn import_milestone_diffs<R: Read>(
                 balance_diffs
                     .merge(tmp_balance_diffs)
                     .expect("can not merge balance diffs");
+                if let Some((consumed_amount, _)) = treasury_change {
+                    *treasury = consumed_amount;
+                }
                 ledger_index = MilestoneIndex(*ledger_index - 1)
             }
             _ => panic!("unexpected diff index"),
@@ -119,7 +152,7 @@ async fn import_milestone_diffs<R: Read>(
     }
 }
 
-async fn read_full_snapshot(full_path: &Path) -> (MilestoneIndex, BalanceDiffs) {
+async fn read_full_snapshot(full_path: &Path) -> (MilestoneIndex, BalanceDiffs, u64) {
     println!("reading full snapshot...");
 
     let mut reader = BufReader::new(
@@ -135,6 +168,9 @@ async fn read_full_snapshot(full_path: &Path) -> (MilestoneIndex, BalanceDiffs)
         let _ = SolidEntryPoint::unpack(&mut reader).expect("can not read solid entry point");
     }
 
+    // the treasury output of the full snapshot belongs to its ledger index
+    let mut treasury = full_header.treasury_output_amount();
+
     let mut balance_diffs = BalanceDiffs::new();
     for _ in 0..full_header.output_count() {
         let _ = MessageId::unpack(&mut reader).expect("can not read message id of output");
@@ -161,6 +197,8 @@ async fn read_full_snapshot(full_path: &Path) -> (MilestoneIndex, BalanceDiffs)
                     .dust_allowance_add(*output.address(), output.amount())
                     .expect("can not add dust allowance");
             }
+            // the treasury output is part of the full snapshot header
+            Output::Treasury(_) => {}
             _ => panic!("unsupported output type"),
         }
     }
@@ -170,15 +208,20 @@ async fn read_full_snapshot(full_path: &Path) -> (MilestoneIndex, BalanceDiffs)
         header.ledger_index(),
         full_header.milestone_diff_count(),
         &mut balance_diffs,
+        &mut treasury,
     )
     .await;
 
     println!("full snapshot successfully read");
 
-    (header.sep_index(), balance_diffs)
+    (header.sep_index(), balance_diffs, treasury)
 }
 
-async fn read_delta_snapshot(delta_path: &Path, mut balance_diffs: BalanceDiffs) -> (MilestoneIndex, BalanceDiffs) {
+async fn read_delta_snapshot(
+    delta_path: &Path,
+    mut balance_diffs: BalanceDiffs,
+    mut treasury: u64,
+) -> (MilestoneIndex, BalanceDiffs, u64) {
     println!("reading delta snapshot...");
 
     let mut reader = BufReader::new(
@@ -201,12 +244,13 @@ async fn read_delta_snapshot(delta_path: &Path, mut balance_diffs: BalanceDiffs)
         header.ledger_index(),
         delta_header.milestone_diff_count(),
         &mut balance_diffs,
+        &mut treasury,
     )
     .await;
 
     println!("delta snapshot successfully read");
 
-    (sep_index, balance_diffs)
+    (sep_index, balance_diffs, treasury)
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -220,7 +264,7 @@ async fn save_sep_index(sep_index: MilestoneIndex) {
     fs::write("sep_index", sep_index.to_string()).expect("cannot write to sep_index file");
 }
 
-async fn save_balance_diffs(balance_diffs: BalanceDiffs, config: &Config) {
+async fn save_balance_diffs(balance_diffs: BalanceDiffs, treasury: u64, config: &Config) {
     let mut json_entries = Vec::new();
 
     for (addr, balance_diff) in balance_diffs {
@@ -237,6 +281,16 @@ async fn save_balance_diffs(balance_diffs: BalanceDiffs, config: &Config) {
         }
     }
 
+    if treasury > 0 {
+        json_entries.push(BootstrapBalanceEntry {
+            account_identifier: AccountIdentifier {
+                address: TREASURY_ACCOUNT.to_string(),
+            },
+            currency: iota_currency(),
+            value: treasury.to_string(),
+        });
+    }
+
     fs::write(
         "bootstrap_balances.json",
         serde_json::to_string_pretty(&json_entries).unwrap(),